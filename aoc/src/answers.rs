@@ -0,0 +1,265 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::Result;
+
+use crate::days::DAYS;
+
+/// Known-good answers for one input. Parts without an answer are not
+/// checked.
+#[derive(Debug, Default, PartialEq)]
+pub struct Expected {
+	pub part1: Option<String>,
+	pub part2: Option<String>,
+}
+
+/// A day's input along with its known-good answers.
+#[derive(Debug, PartialEq)]
+pub struct Case {
+	pub day: usize,
+	pub input: PathBuf,
+	pub expected: Expected,
+}
+
+/// An answer that differs from the known-good answer.
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+	pub part: &'static str,
+	pub expected: String,
+	pub actual: String,
+}
+
+/// Result of checking a single case.
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+	Pass,
+	Skipped,
+	Fail(String),
+	Mismatch(Vec<Mismatch>),
+}
+
+// Converts an answer to a string, allowing numbers to be written bare
+fn answer(value: &toml::Value) -> Option<String> {
+	match value {
+		toml::Value::String(s) => Some(s.trim_end_matches('\n').to_owned()),
+		toml::Value::Integer(n) => Some(n.to_string()),
+		_ => None,
+	}
+}
+
+fn day(key: &str) -> Option<usize> {
+	key.strip_prefix("day")
+		.and_then(|day| day.parse().ok())
+		.filter(|day| (1..=DAYS.len()).contains(day))
+}
+
+/// Parses an answers file. Each `[dayNN]` table maps input paths, relative
+/// to `base`, to a table of `part1` and `part2` answers.
+///
+/// ```toml
+/// [day07."inputs/examples/day07.txt"]
+/// part1 = "CABDFE"
+/// part2 = 253
+/// ```
+pub fn parse(s: &str, base: &Path) -> Result<Vec<Case>> {
+	let table = s.parse::<toml::Value>()?;
+	let table = table.as_table().ok_or("Expected a table of days")?;
+	let mut cases = Vec::new();
+
+	for (key, inputs) in table {
+		let day = day(key).ok_or_else(|| format!("Invalid day: {}", key))?;
+		let inputs = inputs
+			.as_table()
+			.ok_or_else(|| format!("Expected a table of inputs for {}", key))?;
+
+		for (input, parts) in inputs {
+			let parts = parts.as_table().ok_or_else(|| {
+				format!("Expected a table of answers for {}", input)
+			})?;
+
+			let mut expected = Expected::default();
+
+			for (part, value) in parts {
+				let value = answer(value).ok_or_else(|| {
+					format!("Expected a string or integer for {}", part)
+				})?;
+
+				match part.as_str() {
+					"part1" => expected.part1 = Some(value),
+					"part2" => expected.part2 = Some(value),
+					_ => return Err(format!("Unknown part: {}", part).into()),
+				}
+			}
+
+			cases.push(Case {
+				day,
+				input: base.join(input),
+				expected,
+			});
+		}
+	}
+
+	Ok(cases)
+}
+
+/// Reads and parses the answers file at `path`.
+pub fn load(path: &Path) -> Result<Vec<Case>> {
+	let s = fs::read_to_string(path)
+		.map_err(|err| format!("{}: {}", path.display(), err))?;
+
+	parse(&s, path.parent().unwrap_or_else(|| Path::new("")))
+}
+
+impl Case {
+	/// Solves the input and compares with the known-good answers. Missing
+	/// inputs are skipped.
+	pub fn check(&self) -> Outcome {
+		if !self.input.is_file() {
+			return Outcome::Skipped;
+		}
+
+		let answers = fs::read_to_string(&self.input)
+			.map_err(|err| err.into())
+			.and_then(|input| DAYS[self.day - 1](&input));
+
+		let answers = match answers {
+			Ok(answers) => answers,
+			Err(err) => return Outcome::Fail(err.to_string()),
+		};
+
+		let mismatches = vec![
+			("Part 1", &self.expected.part1, answers.part1),
+			("Part 2", &self.expected.part2, answers.part2),
+		]
+		.into_iter()
+		.filter_map(|(part, expected, actual)| {
+			let expected = expected.as_ref()?;
+
+			if *expected == actual {
+				return None;
+			}
+
+			Some(Mismatch {
+				part,
+				expected: expected.to_owned(),
+				actual,
+			})
+		})
+		.collect::<Vec<_>>();
+
+		if mismatches.is_empty() {
+			Outcome::Pass
+		} else {
+			Outcome::Mismatch(mismatches)
+		}
+	}
+}
+
+impl fmt::Display for Mismatch {
+	/// Line by line diff with `-` for expected and `+` for actual lines.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut expected = self.expected.lines();
+		let mut actual = self.actual.lines();
+
+		writeln!(f, "  {}:", self.part)?;
+
+		loop {
+			match (expected.next(), actual.next()) {
+				(None, None) => return Ok(()),
+				(Some(e), Some(a)) if e == a => writeln!(f, "      {}", e)?,
+				(e, a) => {
+					if let Some(e) = e {
+						writeln!(f, "    - {}", e)?;
+					}
+
+					if let Some(a) = a {
+						writeln!(f, "    + {}", a)?;
+					}
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse() {
+		let cases = super::parse(
+			r#"
+			[day07."day07.txt"]
+			part1 = "CABDFE"
+			part2 = 253
+
+			[day11."day11.txt"]
+			part1 = "33,45"
+			"#,
+			Path::new("inputs"),
+		)
+		.unwrap();
+
+		assert_eq!(
+			cases,
+			vec![
+				Case {
+					day: 7,
+					input: "inputs/day07.txt".into(),
+					expected: Expected {
+						part1: Some("CABDFE".to_owned()),
+						part2: Some("253".to_owned()),
+					},
+				},
+				Case {
+					day: 11,
+					input: "inputs/day11.txt".into(),
+					expected: Expected {
+						part1: Some("33,45".to_owned()),
+						part2: None,
+					},
+				},
+			]
+		);
+
+		assert!(super::parse("[day15.\"day15.txt\"]", Path::new("")).is_err());
+		assert!(
+			super::parse("[day01.\"a\"]\npart3 = 1", Path::new("")).is_err()
+		);
+	}
+
+	#[test]
+	fn diff() {
+		let mismatch = Mismatch {
+			part: "Part 1",
+			expected: "#..\n.#.\n..#".to_owned(),
+			actual: "#..\n..#".to_owned(),
+		};
+
+		assert_eq!(
+			mismatch.to_string(),
+			"  Part 1:\n      #..\n    - .#.\n    + ..#\n    - ..#\n"
+		);
+	}
+
+	// Slow in debug builds; run with `cargo test --release -- --ignored`
+	#[test]
+	#[ignore]
+	fn answers() {
+		let path =
+			Path::new(env!("CARGO_MANIFEST_DIR")).join("../answers.toml");
+
+		for case in load(&path).unwrap() {
+			let outcome = case.check();
+
+			assert!(
+				outcome == Outcome::Pass || outcome == Outcome::Skipped,
+				"Day {:02} {}: {:?}",
+				case.day,
+				case.input.display(),
+				outcome
+			);
+		}
+	}
+}
@@ -0,0 +1,36 @@
+use common::{Result, Solution};
+
+pub type Solver = fn(&str) -> Result<Answers>;
+
+// Indexed by `day - 1`
+pub const DAYS: [Solver; 14] = [
+	solve::<day01::Day01>,
+	solve::<day02::Day02>,
+	solve::<day03::Day03>,
+	solve::<day04::Day04>,
+	solve::<day05::Day05>,
+	solve::<day06::Day06>,
+	solve::<day07::Day07>,
+	solve::<day08::Day08>,
+	solve::<day09::Day09>,
+	solve::<day10::Day10>,
+	solve::<day11::Day11>,
+	solve::<day12::Day12>,
+	solve::<day13::Day13>,
+	solve::<day14::Day14>,
+];
+
+#[derive(Debug, PartialEq)]
+pub struct Answers {
+	pub part1: String,
+	pub part2: String,
+}
+
+fn solve<S: Solution>(input: &str) -> Result<Answers> {
+	let day = S::parse(input)?;
+
+	Ok(Answers {
+		part1: day.part1()?.to_string(),
+		part2: day.part2()?.to_string(),
+	})
+}
@@ -0,0 +1,311 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process;
+
+mod answers;
+mod days;
+
+use common::Result;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use answers::Outcome;
+use days::{Answers, DAYS};
+
+static USAGE: &str = r#"Usage:
+    aoc run <day> [--input <path> | <value>]
+    aoc run all [--inputs <dir>]
+    aoc check [<answers>]
+
+Input for a single day is read from `--input <path>` (`-` for stdin), from
+an inline `<value>` (e.g. the Day 11 serial or Day 14 recipe count) or from
+stdin when neither is given. `all` reads `<dir>/dayNN.txt` for every day,
+defaulting to the `inputs` directory, running the days concurrently when
+built with the `parallel` feature.
+
+`check` solves every input listed in an answers file, defaulting to
+`answers.toml`, and reports any answer that differs from the known-good
+one."#;
+
+#[derive(Debug, PartialEq)]
+enum Input {
+	Stdin,
+	File(PathBuf),
+	Inline(String),
+}
+
+impl Input {
+	fn read(&self) -> Result<String> {
+		Ok(match self {
+			Input::Stdin => {
+				let mut buf = String::new();
+
+				io::stdin().read_to_string(&mut buf)?;
+
+				buf
+			}
+			Input::File(path) => fs::read_to_string(path)?,
+			Input::Inline(value) => value.to_owned(),
+		})
+	}
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+	Day(usize, Input),
+	All(PathBuf),
+	Check(PathBuf),
+}
+
+impl Command {
+	fn parse(args: &[String]) -> Result<Self> {
+		let mut args = args.iter().map(|arg| arg.as_str());
+
+		match args.next() {
+			Some("run") => {}
+			Some("check") => {
+				let path = args.next().unwrap_or("answers.toml");
+
+				if args.next().is_some() {
+					return Err("Unexpected arguments".into());
+				}
+
+				return Ok(Command::Check(path.into()));
+			}
+			Some(command) => {
+				return Err(format!("Unknown command: {}", command).into())
+			}
+			None => return Err("Missing command".into()),
+		}
+
+		let day = args.next().ok_or("Missing day")?;
+
+		if day == "all" {
+			let dir = match (args.next(), args.next()) {
+				(None, _) => "inputs",
+				(Some("--inputs"), Some(dir)) => dir,
+				_ => return Err("Expected `--inputs <dir>`".into()),
+			};
+
+			return Ok(Command::All(dir.into()));
+		}
+
+		let day = day
+			.parse::<usize>()
+			.ok()
+			.filter(|day| (1..=DAYS.len()).contains(day))
+			.ok_or_else(|| format!("Invalid day: {}", day))?;
+
+		let input = match (args.next(), args.next()) {
+			(None, _) => Input::Stdin,
+			(Some("--input"), Some("-")) => Input::Stdin,
+			(Some("--input"), Some(path)) => Input::File(path.into()),
+			(Some("--input"), None) => return Err("Missing input path".into()),
+			(Some(value), None) => Input::Inline(value.to_owned()),
+			_ => return Err("Unexpected arguments".into()),
+		};
+
+		if args.next().is_some() {
+			return Err("Unexpected arguments".into());
+		}
+
+		Ok(Command::Day(day, input))
+	}
+}
+
+fn print_part(name: &str, answer: &str) {
+	if answer.contains('\n') {
+		println!("  {}:", name);
+		answer.lines().for_each(|line| println!("    {}", line));
+	} else {
+		println!("  {}: {}", name, answer);
+	}
+}
+
+fn print_answers(day: usize, answers: &Answers) {
+	println!("Day {:02}", day);
+	print_part("Part 1", &answers.part1);
+	print_part("Part 2", &answers.part2);
+}
+
+fn run_day(day: usize, input: &Input) -> Result<()> {
+	let answers = DAYS[day - 1](&input.read()?)?;
+
+	print_answers(day, &answers);
+
+	Ok(())
+}
+
+// Outcome of running one day as part of `run all`, kept `Send` so days can
+// run on other threads
+enum Run {
+	Skipped(PathBuf),
+	Solved(Answers),
+	Failed(String),
+}
+
+// Runs every day, on a thread pool with the `parallel` feature, and prints
+// the results in order once they're all done
+fn run_all(dir: &Path) -> bool {
+	let days = 1..=DAYS.len();
+
+	#[cfg(feature = "parallel")]
+	let days = days.into_par_iter();
+
+	let runs = days
+		.map(|day| {
+			let path = dir.join(format!("day{:02}.txt", day));
+
+			if !path.is_file() {
+				return Run::Skipped(path);
+			}
+
+			match Input::File(path)
+				.read()
+				.and_then(|input| DAYS[day - 1](&input))
+			{
+				Ok(answers) => Run::Solved(answers),
+				Err(err) => Run::Failed(err.to_string()),
+			}
+		})
+		.collect::<Vec<_>>();
+
+	let mut success = true;
+
+	for (i, run) in runs.into_iter().enumerate() {
+		let day = i + 1;
+
+		match run {
+			Run::Skipped(path) => {
+				println!("Day {:02}", day);
+				println!("  Skipped: missing {}", path.display());
+			}
+			Run::Solved(answers) => print_answers(day, &answers),
+			Run::Failed(err) => {
+				eprintln!("Day {:02} failed: {}", day, err);
+				success = false;
+			}
+		}
+	}
+
+	success
+}
+
+fn check(path: &Path) -> Result<bool> {
+	let (mut passed, mut skipped, mut failed) = (0, 0, 0);
+
+	for case in answers::load(path)? {
+		let name = format!("Day {:02} {}", case.day, case.input.display());
+
+		match case.check() {
+			Outcome::Pass => {
+				passed += 1;
+				println!("{}: pass", name);
+			}
+			Outcome::Skipped => {
+				skipped += 1;
+				println!("{}: skipped, missing input", name);
+			}
+			Outcome::Fail(err) => {
+				failed += 1;
+				println!("{}: fail, {}", name, err);
+			}
+			Outcome::Mismatch(mismatches) => {
+				failed += 1;
+				println!("{}: mismatch", name);
+				mismatches.iter().for_each(|m| print!("{}", m));
+			}
+		}
+	}
+
+	println!(
+		"\n{} passed, {} failed, {} skipped",
+		passed, failed, skipped
+	);
+
+	Ok(failed == 0)
+}
+
+fn main() {
+	let args = env::args().skip(1).collect::<Vec<_>>();
+
+	let command = Command::parse(&args).unwrap_or_else(|err| {
+		eprintln!("{}\n\n{}", err, USAGE);
+		process::exit(2);
+	});
+
+	let success = match command {
+		Command::Day(day, input) => match run_day(day, &input) {
+			Ok(()) => true,
+			Err(err) => {
+				eprintln!("Day {:02} failed: {}", day, err);
+				false
+			}
+		},
+		Command::All(dir) => run_all(&dir),
+		Command::Check(path) => check(&path).unwrap_or_else(|err| {
+			eprintln!("Check failed: {}", err);
+			false
+		}),
+	};
+
+	if !success {
+		process::exit(1);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn args(s: &str) -> Vec<String> {
+		s.split_whitespace().map(|arg| arg.to_owned()).collect()
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(
+			Command::parse(&args("run 7 --input inputs/day07.txt")).unwrap(),
+			Command::Day(7, Input::File("inputs/day07.txt".into()))
+		);
+
+		assert_eq!(
+			Command::parse(&args("run 11 18")).unwrap(),
+			Command::Day(11, Input::Inline("18".to_owned()))
+		);
+
+		assert_eq!(
+			Command::parse(&args("run 01 --input -")).unwrap(),
+			Command::Day(1, Input::Stdin)
+		);
+
+		assert_eq!(
+			Command::parse(&args("run all")).unwrap(),
+			Command::All("inputs".into())
+		);
+
+		assert_eq!(
+			Command::parse(&args("check")).unwrap(),
+			Command::Check("answers.toml".into())
+		);
+
+		assert!(Command::parse(&args("run 15")).is_err());
+		assert!(Command::parse(&args("walk 1")).is_err());
+	}
+
+	#[test]
+	fn run() {
+		let answers = DAYS[6](
+			r#"
+			Step C must be finished before step A can begin.
+			Step C must be finished before step F can begin.
+			Step A must be finished before step B can begin.
+			"#,
+		)
+		.unwrap();
+
+		assert_eq!(answers.part1, "CABF");
+	}
+}
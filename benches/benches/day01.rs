@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day01::generate::generate;
+use day01::Day01;
+
+fn day01(c: &mut Criterion) {
+	// 1000 frequency changes
+	let generated = generate(&mut Rng::new(1), 1000);
+
+	bench_solution::<Day01>(c, "example", &example(1));
+	bench_solution::<Day01>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day01);
+criterion_main!(benches);
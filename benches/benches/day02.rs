@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day02::generate::generate;
+use day02::Day02;
+
+fn day02(c: &mut Criterion) {
+	// 250 box IDs
+	let generated = generate(&mut Rng::new(2), 250);
+
+	bench_solution::<Day02>(c, "example", &example(2));
+	bench_solution::<Day02>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day02);
+criterion_main!(benches);
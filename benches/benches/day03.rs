@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day03::generate::generate;
+use day03::Day03;
+
+fn day03(c: &mut Criterion) {
+	// 1300 claims
+	let generated = generate(&mut Rng::new(3), 1300);
+
+	bench_solution::<Day03>(c, "example", &example(3));
+	bench_solution::<Day03>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day03);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day04::generate::generate;
+use day04::Day04;
+
+fn day04(c: &mut Criterion) {
+	// Logs for 336 shifts
+	let generated = generate(&mut Rng::new(4), 336);
+
+	bench_solution::<Day04>(c, "example", &example(4));
+	bench_solution::<Day04>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day04);
+criterion_main!(benches);
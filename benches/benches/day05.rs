@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day05::generate::generate;
+use day05::Day05;
+
+fn day05(c: &mut Criterion) {
+	// A polymer of 50,000 units
+	let generated = generate(&mut Rng::new(5), 50_000);
+
+	bench_solution::<Day05>(c, "example", &example(5));
+	bench_solution::<Day05>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day05);
+criterion_main!(benches);
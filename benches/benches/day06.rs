@@ -0,0 +1,20 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day06::generate::generate;
+use day06::Day06;
+
+fn day06(c: &mut Criterion) {
+	// 50 coordinates
+	let generated = generate(&mut Rng::new(6), 50);
+
+	bench_solution::<Day06>(c, "example", &example(6));
+	bench_solution::<Day06>(c, "generated", &generated.input);
+}
+
+criterion_group! {
+	name = benches;
+	config = Criterion::default().sample_size(10);
+	targets = day06
+}
+criterion_main!(benches);
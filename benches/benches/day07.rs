@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day07::generate::generate;
+use day07::Day07;
+
+fn day07(c: &mut Criterion) {
+	// Dependencies between all 26 steps
+	let generated = generate(&mut Rng::new(7), 26);
+
+	bench_solution::<Day07>(c, "example", &example(7));
+	bench_solution::<Day07>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day07);
+criterion_main!(benches);
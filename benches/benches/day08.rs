@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day08::generate::generate;
+use day08::Day08;
+
+fn day08(c: &mut Criterion) {
+	// A tree of 2000 nodes
+	let generated = generate(&mut Rng::new(8), 2000);
+
+	bench_solution::<Day08>(c, "example", &example(8));
+	bench_solution::<Day08>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day08);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day09::generate::generate;
+use day09::Day09;
+
+fn day09(c: &mut Criterion) {
+	// A game about the size of a puzzle input
+	let generated = generate(&mut Rng::new(9), 71_000);
+
+	bench_solution::<Day09>(c, "example", &example(9));
+	bench_solution::<Day09>(c, "generated", &generated.input);
+}
+
+criterion_group! {
+	name = benches;
+	config = Criterion::default().sample_size(10);
+	targets = day09
+}
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day10::generate::generate;
+use day10::Day10;
+
+fn day10(c: &mut Criterion) {
+	// 350 points of light
+	let generated = generate(&mut Rng::new(10), 350);
+
+	bench_solution::<Day10>(c, "example", &example(10));
+	bench_solution::<Day10>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day10);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day11::generate::generate;
+use day11::Day11;
+
+fn day11(c: &mut Criterion) {
+	// A random grid serial number
+	let generated = generate(&mut Rng::new(11), 0);
+
+	bench_solution::<Day11>(c, "example", &example(11));
+	bench_solution::<Day11>(c, "generated", &generated.input);
+}
+
+criterion_group! {
+	name = benches;
+	config = Criterion::default().sample_size(10);
+	targets = day11
+}
+criterion_main!(benches);
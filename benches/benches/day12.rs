@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day12::generate::generate;
+use day12::Day12;
+
+fn day12(c: &mut Criterion) {
+	// 100 pots
+	let generated = generate(&mut Rng::new(12), 100);
+
+	bench_solution::<Day12>(c, "example", &example(12));
+	bench_solution::<Day12>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day12);
+criterion_main!(benches);
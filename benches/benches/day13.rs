@@ -0,0 +1,16 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day13::generate::generate;
+use day13::Day13;
+
+fn day13(c: &mut Criterion) {
+	// 21 carts
+	let generated = generate(&mut Rng::new(13), 21);
+
+	bench_solution::<Day13>(c, "example", &example(13));
+	bench_solution::<Day13>(c, "generated", &generated.input);
+}
+
+criterion_group!(benches, day13);
+criterion_main!(benches);
@@ -0,0 +1,20 @@
+use benches::{bench_solution, example};
+use common::generate::Rng;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day14::generate::generate;
+use day14::Day14;
+
+fn day14(c: &mut Criterion) {
+	// A six digit number of recipes
+	let generated = generate(&mut Rng::new(14), 1_000_000);
+
+	bench_solution::<Day14>(c, "example", &example(14));
+	bench_solution::<Day14>(c, "generated", &generated.input);
+}
+
+criterion_group! {
+	name = benches;
+	config = Criterion::default().sample_size(10);
+	targets = day14
+}
+criterion_main!(benches);
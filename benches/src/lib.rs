@@ -0,0 +1,32 @@
+//! Shared setup for the benchmarks of every day
+
+use std::fs;
+use std::path::Path;
+
+use common::Solution;
+use criterion::{black_box, Criterion};
+
+/// The puzzle statement example for `day` from `inputs/examples`.
+pub fn example(day: u32) -> String {
+	let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+		.join(format!("../inputs/examples/day{:02}.txt", day));
+
+	fs::read_to_string(&path)
+		.unwrap_or_else(|err| panic!("{}: {}", path.display(), err))
+}
+
+/// Benchmarks parsing `input` and solving each part separately, named
+/// `dayNN/<name>/parse`, `dayNN/<name>/part1` and `dayNN/<name>/part2`.
+pub fn bench_solution<S: Solution>(c: &mut Criterion, name: &str, input: &str) {
+	let mut group = c.benchmark_group(format!("day{:02}/{}", S::DAY, name));
+
+	group.bench_function("parse", |b| {
+		b.iter(|| S::parse(black_box(input)).unwrap())
+	});
+
+	let day = S::parse(input).unwrap();
+
+	group.bench_function("part1", |b| b.iter(|| day.part1()));
+	group.bench_function("part2", |b| b.iter(|| day.part2()));
+	group.finish();
+}
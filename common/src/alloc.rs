@@ -0,0 +1,96 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator that keeps track of current and peak heap usage.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: common::alloc::Counting = common::alloc::Counting;
+/// ```
+pub struct Counting;
+
+fn grow(n: usize) {
+	let current = CURRENT.fetch_add(n, Ordering::Relaxed) + n;
+
+	PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+fn shrink(n: usize) {
+	CURRENT.fetch_sub(n, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		let ptr = System.alloc(layout);
+
+		if !ptr.is_null() {
+			INSTALLED.store(true, Ordering::Relaxed);
+			grow(layout.size());
+		}
+
+		ptr
+	}
+
+	unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+		let ptr = System.alloc_zeroed(layout);
+
+		if !ptr.is_null() {
+			INSTALLED.store(true, Ordering::Relaxed);
+			grow(layout.size());
+		}
+
+		ptr
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		System.dealloc(ptr, layout);
+		shrink(layout.size());
+	}
+
+	unsafe fn realloc(
+		&self,
+		ptr: *mut u8,
+		layout: Layout,
+		new_size: usize,
+	) -> *mut u8 {
+		let ptr = System.realloc(ptr, layout, new_size);
+
+		if !ptr.is_null() {
+			if new_size > layout.size() {
+				grow(new_size - layout.size());
+			} else {
+				shrink(layout.size() - new_size);
+			}
+		}
+
+		ptr
+	}
+}
+
+/// Whether `Counting` is the global allocator of the running binary.
+pub fn installed() -> bool {
+	INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Bytes currently allocated.
+pub fn current() -> usize {
+	CURRENT.load(Ordering::Relaxed)
+}
+
+/// Most bytes allocated at once since the last `reset_peak`.
+pub fn peak() -> usize {
+	PEAK.load(Ordering::Relaxed)
+}
+
+/// Starts tracking a new peak from the current usage, which is returned.
+pub fn reset_peak() -> usize {
+	let current = current();
+
+	PEAK.store(current, Ordering::Relaxed);
+
+	current
+}
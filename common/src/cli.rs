@@ -0,0 +1,753 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::report::{self, Report};
+use crate::{alloc, ParseError, Solution};
+
+/// How a day binary prints its answers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+	/// Free-form text describing each answer
+	Text,
+	/// A single JSON report, see `Report::to_json`
+	Json,
+}
+
+/// Command line arguments shared by every day binary.
+#[derive(Debug, PartialEq)]
+pub struct Args {
+	pub format: Format,
+	/// Print how long each step took and how much it allocated
+	pub timings: bool,
+	/// Print how the answers were reached, e.g. every Day 5 reaction
+	pub trace: Option<Format>,
+	/// Read inputs a chunk at a time where a day supports it, e.g. Day 5
+	/// polymers too large to hold in memory
+	pub stream: bool,
+	/// Paths given with `--input`, `-` for stdin
+	pub inputs: Vec<String>,
+	/// Positional arguments, e.g. the Day 11 serial number
+	pub values: Vec<String>,
+	/// Path given with `--rules`, e.g. the Day 5 reaction rules
+	pub rules: Option<String>,
+	/// How many repeats to list when tracing, e.g. Day 1 repeated frequencies
+	pub repeats: Option<usize>,
+	/// Path given with `--output` to write results to, e.g. the Day 5
+	/// polymer, `-` for stdout
+	pub output: Option<String>,
+}
+
+/// Options only some days take, declared by each binary so that the rest
+/// reject them rather than ignore them. `--format`, `--timings` and
+/// `--input` are always taken.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Options {
+	/// `--trace text|json`
+	pub trace: bool,
+	/// `--stream`
+	pub stream: bool,
+	/// `--rules <path>`
+	pub rules: bool,
+	/// `--repeats <n>`, which also needs `--trace`
+	pub repeats: bool,
+	/// `--output <path>`
+	pub output: bool,
+	/// Positional `<value>` arguments
+	pub values: bool,
+}
+
+impl Options {
+	/// Just the options every day takes.
+	pub const NONE: Options = Options {
+		trace: false,
+		stream: false,
+		rules: false,
+		repeats: false,
+		output: false,
+		values: false,
+	};
+}
+
+/// A puzzle input and where it came from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Input {
+	/// Path of the file, `<stdin>` or the value itself for positional
+	/// arguments
+	pub name: String,
+	pub text: String,
+}
+
+impl FromStr for Format {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, ()> {
+		match s {
+			"text" => Ok(Format::Text),
+			"json" => Ok(Format::Json),
+			_ => Err(()),
+		}
+	}
+}
+
+impl Args {
+	/// Parses `[--format text|json] [--timings] [--input <path>...]` along
+	/// with whichever of `[--trace text|json] [--stream] [--rules <path>]
+	/// [--repeats <n>] [--output <path>] [<value>...]` are in `options`.
+	pub fn parse<I>(args: I, options: Options) -> Result<Self, String>
+	where
+		I: IntoIterator<Item = String>,
+	{
+		let mut args = args.into_iter();
+		let mut format = Format::Text;
+		let mut timings = false;
+		let mut trace = None;
+		let mut stream = false;
+		let mut inputs = Vec::new();
+		let mut values = Vec::new();
+		let mut rules = None;
+		let mut repeats = None;
+		let mut output = None;
+
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"--format" => {
+					format = match args.next().as_deref().map(str::parse) {
+						Some(Ok(format)) => format,
+						_ => return Err("Expected `--format text|json`".into()),
+					}
+				}
+				"--timings" => timings = true,
+				"--stream" if options.stream => stream = true,
+				"--trace" if options.trace => {
+					trace = match args.next().as_deref().map(str::parse) {
+						Some(Ok(format)) => Some(format),
+						_ => return Err("Expected `--trace text|json`".into()),
+					}
+				}
+				"--input" => match args.next() {
+					Some(path) => inputs.push(path),
+					None => return Err("Expected `--input <path>`".into()),
+				},
+				"--rules" if options.rules => match args.next() {
+					Some(path) => rules = Some(path),
+					None => return Err("Expected `--rules <path>`".into()),
+				},
+				"--output" if options.output => match args.next() {
+					Some(path) => output = Some(path),
+					None => return Err("Expected `--output <path>`".into()),
+				},
+				"--repeats" if options.repeats => {
+					repeats = match args.next().map(|n| n.parse()) {
+						Some(Ok(n)) => Some(n),
+						_ => return Err("Expected `--repeats <n>`".into()),
+					}
+				}
+				"--trace" | "--stream" | "--rules" | "--repeats"
+				| "--output" => {
+					return Err(format!("Not an option of this day: {}", arg));
+				}
+				flag if flag.starts_with("--") => {
+					return Err(format!("Unknown option: {}", flag));
+				}
+				_ if options.values => values.push(arg),
+				_ => return Err(format!("Unexpected argument: {}", arg)),
+			}
+		}
+
+		// A trace is printed in place of the answers, in a format of its own,
+		// and is all that lists repeats
+		if trace.is_some() && format == Format::Json {
+			return Err("Expected either `--format json` or `--trace`".into());
+		}
+		if trace.is_some() && stream {
+			return Err("Expected either `--stream` or `--trace`".into());
+		}
+		// Streamed answers are printed as they're worked out, without a report
+		if stream && format == Format::Json {
+			return Err("Expected either `--format json` or `--stream`".into());
+		}
+		// A written polymer goes with the text answers, not the report
+		if output.is_some() && format == Format::Json {
+			return Err("Expected either `--format json` or `--output`".into());
+		}
+		if repeats.is_some() && trace.is_none() {
+			return Err(
+				"Expected `--trace text|json` with `--repeats <n>`".into()
+			);
+		}
+
+		Ok(Args {
+			format,
+			timings,
+			trace,
+			stream,
+			inputs,
+			values,
+			rules,
+			repeats,
+			output,
+		})
+	}
+
+	/// Parses the arguments of the running binary, taking `options` as well
+	/// as those every day takes, and exiting on failure.
+	pub fn from_env(options: Options) -> Self {
+		Self::parse(env::args().skip(1), options).unwrap_or_else(|err| {
+			eprintln!("{}", err);
+			process::exit(2);
+		})
+	}
+
+	/// Reads every input given with `--input`, or stdin if there are none.
+	/// Directories are replaced by the files directly inside them, in name
+	/// order. Exits if any input can't be read.
+	pub fn read_inputs(&self) -> Vec<Input> {
+		if self.inputs.is_empty() {
+			match stdin() {
+				Ok(input) => return vec![input],
+				Err(err) => {
+					eprintln!("Failed to read <stdin>: {}", err);
+					process::exit(2);
+				}
+			}
+		}
+
+		let mut inputs = Vec::new();
+
+		for path in &self.inputs {
+			let read = if path == "-" {
+				stdin().map(|input| vec![input])
+			} else {
+				read_path(Path::new(path))
+			};
+
+			match read {
+				Ok(read) => inputs.extend(read),
+				Err(err) => {
+					eprintln!("Failed to read {}: {}", path, err);
+					process::exit(2);
+				}
+			}
+		}
+
+		inputs
+	}
+
+	/// Opens every input like [`read_inputs`](Self::read_inputs) without
+	/// reading it, for inputs too large to hold in memory. Exits if any input
+	/// can't be opened.
+	pub fn open_inputs(&self) -> Vec<(String, Box<dyn Read>)> {
+		if self.inputs.is_empty() {
+			return vec![("<stdin>".to_owned(), Box::new(io::stdin()))];
+		}
+
+		let mut inputs = Vec::<(String, Box<dyn Read>)>::new();
+
+		for path in &self.inputs {
+			if path == "-" {
+				inputs.push(("<stdin>".to_owned(), Box::new(io::stdin())));
+				continue;
+			}
+
+			let opened = files(Path::new(path)).and_then(|files| {
+				files
+					.into_iter()
+					.map(|file| {
+						let reader = File::open(&file)?;
+
+						Ok((file.display().to_string(), reader))
+					})
+					.collect::<io::Result<Vec<_>>>()
+			});
+
+			match opened {
+				Ok(opened) => {
+					inputs.extend(opened.into_iter().map(|(name, reader)| {
+						(name, Box::new(reader) as Box<dyn Read>)
+					}))
+				}
+				Err(err) => {
+					eprintln!("Failed to read {}: {}", path, err);
+					process::exit(2);
+				}
+			}
+		}
+
+		inputs
+	}
+
+	/// Reads the file given with `--rules`, if any. Exits if it can't be read.
+	pub fn read_rules(&self) -> Option<Input> {
+		let path = self.rules.as_ref()?;
+
+		match fs::read_to_string(path) {
+			Ok(text) => Some(Input {
+				name: path.to_owned(),
+				text,
+			}),
+			Err(err) => {
+				eprintln!("Failed to read {}: {}", path, err);
+				process::exit(2);
+			}
+		}
+	}
+
+	/// Creates the file given with `--output`, if any, or stdout for `-`.
+	/// Exits if it can't be created.
+	pub fn create_output(&self) -> Option<Box<dyn Write>> {
+		let path = self.output.as_ref()?;
+
+		if path == "-" {
+			return Some(Box::new(io::stdout()));
+		}
+
+		match File::create(path) {
+			Ok(file) => Some(Box::new(BufWriter::new(file))),
+			Err(err) => {
+				eprintln!("Failed to create {}: {}", path, err);
+				process::exit(2);
+			}
+		}
+	}
+
+	/// Each positional argument as an input of its own, e.g. Day 11 serial
+	/// numbers, or the inputs given with `--input` if there are none.
+	pub fn value_inputs(&self) -> Vec<Input> {
+		if self.values.is_empty() {
+			return self.read_inputs();
+		}
+
+		self.values
+			.iter()
+			.map(|value| Input {
+				name: value.to_owned(),
+				text: value.to_owned(),
+			})
+			.collect()
+	}
+}
+
+fn stdin() -> io::Result<Input> {
+	Ok(Input {
+		name: "<stdin>".to_owned(),
+		text: read_stdin()?,
+	})
+}
+
+// The file at `path`, or every file directly inside it if it's a directory
+fn read_path(path: &Path) -> io::Result<Vec<Input>> {
+	files(path)?
+		.into_iter()
+		.map(|path| {
+			Ok(Input {
+				name: path.display().to_string(),
+				text: fs::read_to_string(&path)?,
+			})
+		})
+		.collect()
+}
+
+// `path` itself, or every file directly inside it if it's a directory, in
+// name order
+fn files(path: &Path) -> io::Result<Vec<PathBuf>> {
+	if !path.is_dir() {
+		return Ok(vec![path.to_owned()]);
+	}
+
+	let mut paths = fs::read_dir(path)?
+		.map(|entry| Ok(entry?.path()))
+		.collect::<io::Result<Vec<_>>>()?;
+
+	paths.retain(|path| path.is_file());
+	paths.sort();
+
+	Ok(paths)
+}
+
+/// Measures each step of a binary when enabled, for printing after the
+/// answers.
+pub struct Profiler {
+	enabled: bool,
+	steps: Vec<(String, Duration, usize)>,
+}
+
+impl Profiler {
+	/// Creates a profiler that only measures when `enabled`.
+	pub fn new(enabled: bool) -> Self {
+		Self {
+			enabled,
+			steps: Vec::new(),
+		}
+	}
+
+	/// Runs `f`, recording it as `name`.
+	pub fn step<T>(&mut self, name: &str, f: impl FnOnce() -> T) -> T {
+		if !self.enabled {
+			return f();
+		}
+
+		let (result, time, heap) = report::measure(f);
+
+		self.steps.push((name.to_owned(), time, heap));
+
+		result
+	}
+
+	/// Prints every recorded step, with its peak heap usage only when
+	/// `alloc::Counting` is the global allocator.
+	pub fn print(&self) {
+		if !self.enabled {
+			return;
+		}
+
+		println!("Timings:");
+
+		for (name, time, heap) in &self.steps {
+			let time = time.as_secs_f64() * 1000.0;
+
+			if alloc::installed() {
+				println!(
+					"  {}: {:.3}ms, peak heap {}",
+					name,
+					time,
+					bytes(*heap)
+				);
+			} else {
+				println!("  {}: {:.3}ms", name, time);
+			}
+		}
+	}
+}
+
+// Human readable byte count
+fn bytes(n: usize) -> String {
+	let units = ["B", "KiB", "MiB", "GiB"];
+	let mut size = n as f64;
+	let mut unit = 0;
+
+	while size >= 1024.0 && unit + 1 < units.len() {
+		size /= 1024.0;
+		unit += 1;
+	}
+
+	if unit == 0 {
+		format!("{} B", n)
+	} else {
+		format!("{:.1} {}", size, units[unit])
+	}
+}
+
+/// Reads all of stdin, failing if it isn't valid UTF-8.
+pub fn read_stdin() -> io::Result<String> {
+	let mut input = String::new();
+
+	io::stdin().read_to_string(&mut input)?;
+
+	Ok(input)
+}
+
+/// Solves every input in turn, either printing the JSON report or running
+/// `solve` to print the answers along with any timings. A header names each
+/// input when there is more than one. Exits once every input has been tried
+/// if any failed to parse or solve.
+pub fn run<S, F>(args: &Args, inputs: &[Input], solve: F)
+where
+	S: Solution,
+	F: FnMut(&str, &mut Profiler) -> crate::Result<()>,
+{
+	run_with(args, inputs, Report::run::<S>, solve);
+}
+
+/// Solves every input in turn like [`run`], building the JSON report with
+/// `report` rather than the day's own `Solution`, e.g. with
+/// `Report::run_with` to apply options given on the command line.
+pub fn run_with<R, F>(
+	args: &Args,
+	inputs: &[Input],
+	mut report: R,
+	mut solve: F,
+) where
+	R: FnMut(&str) -> Result<Report, ParseError>,
+	F: FnMut(&str, &mut Profiler) -> crate::Result<()>,
+{
+	let multiple = inputs.len() > 1;
+	let mut failed = false;
+
+	for (i, input) in inputs.iter().enumerate() {
+		let result = match args.format {
+			Format::Json => {
+				report(&input.text).map_err(Into::into).map(|report| {
+					if multiple {
+						println!(
+							r#"{{"input":{},"report":{}}}"#,
+							report::json_string(&input.name),
+							report.to_json()
+						);
+					} else {
+						println!("{}", report.to_json());
+					}
+
+					// The report holds the error of any part that failed
+					failed |= report.failed();
+				})
+			}
+			Format::Text => {
+				if multiple {
+					header(i, &input.name);
+				}
+
+				let mut profiler = Profiler::new(args.timings);
+
+				solve(&input.text, &mut profiler).map(|_| profiler.print())
+			}
+		};
+
+		if let Err(err) = result {
+			let step = if err.is::<ParseError>() {
+				"parse"
+			} else {
+				"solve"
+			};
+
+			eprintln!("Failed to {} {}: {}", step, input.name, err);
+			failed = true;
+		}
+	}
+
+	if failed {
+		process::exit(1);
+	}
+}
+
+/// Solves every input in turn like [`run`], handing `solve` a reader for
+/// the input instead of reading all of it first. Answers are printed as
+/// text, as parsing rejects `--stream` with `--format json`. I/O and parse
+/// errors are reported as failing to read the input, anything else as
+/// failing to solve it. Exits once every input has been tried if any failed.
+pub fn stream<F>(args: &Args, mut solve: F)
+where
+	F: FnMut(Box<dyn Read>, &mut Profiler) -> crate::Result<()>,
+{
+	let inputs = args.open_inputs();
+	let multiple = inputs.len() > 1;
+	let mut failed = false;
+
+	for (i, (name, reader)) in inputs.into_iter().enumerate() {
+		if multiple {
+			header(i, &name);
+		}
+
+		let mut profiler = Profiler::new(args.timings);
+
+		match solve(reader, &mut profiler) {
+			Ok(()) => profiler.print(),
+			Err(err) => {
+				let step = if err.is::<io::Error>() || err.is::<ParseError>() {
+					"read"
+				} else {
+					"solve"
+				};
+
+				eprintln!("Failed to {} {}: {}", step, name, err);
+				failed = true;
+			}
+		}
+	}
+
+	if failed {
+		process::exit(1);
+	}
+}
+
+// Names the `i`th of several inputs before its answers
+fn header(i: usize, name: &str) {
+	if i > 0 {
+		println!();
+	}
+
+	println!("== {} ==", name);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// Every option is allowed
+	const ALL: Options = Options {
+		trace: true,
+		stream: true,
+		rules: true,
+		repeats: true,
+		output: true,
+		values: true,
+	};
+
+	fn args(s: &str) -> Result<Args, String> {
+		args_with(s, ALL)
+	}
+
+	fn args_with(s: &str, options: Options) -> Result<Args, String> {
+		Args::parse(s.split_whitespace().map(|arg| arg.to_owned()), options)
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(
+			args("--format json 18"),
+			Ok(Args {
+				format: Format::Json,
+				timings: false,
+				trace: None,
+				stream: false,
+				inputs: vec![],
+				values: vec!["18".to_owned()],
+				rules: None,
+				repeats: None,
+				output: None,
+			})
+		);
+
+		assert_eq!(
+			args("--timings"),
+			Ok(Args {
+				format: Format::Text,
+				timings: true,
+				trace: None,
+				stream: false,
+				inputs: vec![],
+				values: vec![],
+				rules: None,
+				repeats: None,
+				output: None,
+			})
+		);
+
+		assert_eq!(
+			args("--input a.txt 1 --input -").map(|args| args.inputs),
+			Ok(vec!["a.txt".to_owned(), "-".to_owned()])
+		);
+
+		assert_eq!(
+			args("--trace json").map(|args| args.trace),
+			Ok(Some(Format::Json))
+		);
+
+		assert_eq!(
+			args("--rules rules.txt").map(|args| args.rules),
+			Ok(Some("rules.txt".to_owned()))
+		);
+
+		assert_eq!(
+			args("--trace text --repeats 10").map(|args| args.repeats),
+			Ok(Some(10))
+		);
+
+		assert_eq!(
+			args("--output out.txt").map(|args| args.output),
+			Ok(Some("out.txt".to_owned()))
+		);
+
+		assert!(args("--format yaml").is_err());
+		assert!(args("--trace").is_err());
+		assert!(args("--rules").is_err());
+		assert!(args("--output").is_err());
+		assert!(args("--trace text --repeats ten").is_err());
+		assert!(args("--repeats 10").is_err());
+		assert!(args("--format json --trace json").is_err());
+		assert!(args("--stream --trace text").is_err());
+		assert!(args("--stream --format json").is_err());
+		assert!(args("--output out.txt --format json").is_err());
+		assert!(args("--input").is_err());
+		assert!(args("--verbose").is_err());
+	}
+
+	#[test]
+	fn parse_options() {
+		let none = |s| args_with(s, Options::NONE);
+
+		assert_eq!(
+			none("--timings --input a.txt").map(|args| args.inputs),
+			Ok(vec!["a.txt".to_owned()])
+		);
+		assert_eq!(
+			none("--rules x"),
+			Err("Not an option of this day: --rules".to_owned())
+		);
+		assert_eq!(none("18"), Err("Unexpected argument: 18".to_owned()));
+
+		for s in &["--trace text", "--stream", "--repeats 3", "--output -"] {
+			assert!(none(s).is_err(), "{}", s);
+		}
+
+		let trace = Options {
+			trace: true,
+			..Options::NONE
+		};
+
+		assert!(args_with("--trace text", trace).is_ok());
+		assert!(args_with("--trace text --repeats 3", trace).is_err());
+	}
+
+	#[test]
+	fn read_path() {
+		let dir =
+			env::temp_dir().join(format!("cli-read-path-{}", process::id()));
+
+		fs::create_dir_all(dir.join("nested")).unwrap();
+		fs::write(dir.join("b.txt"), "2").unwrap();
+		fs::write(dir.join("a.txt"), "1").unwrap();
+
+		let inputs = super::read_path(&dir);
+		let single = super::read_path(&dir.join("a.txt"));
+		let missing = super::read_path(&dir.join("c.txt"));
+
+		fs::remove_dir_all(&dir).unwrap();
+
+		let texts = inputs
+			.unwrap()
+			.into_iter()
+			.map(|input| input.text)
+			.collect::<Vec<_>>();
+
+		assert_eq!(texts, ["1", "2"]);
+		assert_eq!(single.unwrap()[0].text, "1");
+		assert!(missing.is_err());
+	}
+
+	#[test]
+	fn open_inputs() {
+		let dir =
+			env::temp_dir().join(format!("cli-open-inputs-{}", process::id()));
+
+		fs::create_dir_all(&dir).unwrap();
+		fs::write(dir.join("b.txt"), "2").unwrap();
+		fs::write(dir.join("a.txt"), "1").unwrap();
+
+		let args = args(&format!("--input {}", dir.display())).unwrap();
+		let texts = args
+			.open_inputs()
+			.into_iter()
+			.map(|(_, mut reader)| {
+				let mut text = String::new();
+
+				reader.read_to_string(&mut text).map(|_| text)
+			})
+			.collect::<io::Result<Vec<_>>>();
+
+		fs::remove_dir_all(&dir).unwrap();
+
+		assert_eq!(texts.unwrap(), ["1", "2"]);
+	}
+
+	#[test]
+	fn bytes() {
+		assert_eq!(super::bytes(512), "512 B");
+		assert_eq!(super::bytes(1536), "1.5 KiB");
+		assert_eq!(super::bytes(3 << 20), "3.0 MiB");
+	}
+}
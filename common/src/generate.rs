@@ -0,0 +1,76 @@
+/// A random puzzle input along with its answers, where they could be worked
+/// out without the solver.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Generated<P1, P2> {
+	pub input: String,
+	pub part1: Option<P1>,
+	pub part2: Option<P2>,
+}
+
+/// A small xorshift generator, so generated inputs are identical from one
+/// run to the next for the same seed.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+	/// Creates a generator from `seed`.
+	pub fn new(seed: u64) -> Self {
+		Rng(seed.max(1))
+	}
+
+	/// Next pseudo-random number.
+	pub fn next_u64(&mut self) -> u64 {
+		self.0 ^= self.0 << 13;
+		self.0 ^= self.0 >> 7;
+		self.0 ^= self.0 << 17;
+		self.0
+	}
+
+	/// Number in `[low, high)`.
+	pub fn range(&mut self, low: i64, high: i64) -> i64 {
+		low + (self.next_u64() % (high - low) as u64) as i64
+	}
+
+	/// Index in `[0, n)`.
+	pub fn below(&mut self, n: usize) -> usize {
+		(self.next_u64() % n as u64) as usize
+	}
+
+	/// True roughly once every `n` calls.
+	pub fn one_in(&mut self, n: usize) -> bool {
+		self.below(n) == 0
+	}
+
+	/// Random lowercase ASCII letter.
+	pub fn letter(&mut self) -> char {
+		(b'a' + self.below(26) as u8) as char
+	}
+
+	/// Shuffles `items` in place.
+	pub fn shuffle<T>(&mut self, items: &mut [T]) {
+		for i in (1..items.len()).rev() {
+			items.swap(i, self.below(i + 1));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rng() {
+		let mut rng = Rng::new(0);
+		let mut items = (0..10).collect::<Vec<_>>();
+
+		assert!((0..1000)
+			.map(|_| rng.range(-3, 3))
+			.all(|n| (-3..3).contains(&n)));
+
+		rng.shuffle(&mut items);
+		items.sort();
+
+		assert_eq!(items, (0..10).collect::<Vec<_>>());
+		assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+	}
+}
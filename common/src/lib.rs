@@ -0,0 +1,41 @@
+//! Interfaces shared by every day's solution
+
+use std::fmt;
+
+pub mod alloc;
+pub mod cli;
+pub mod generate;
+pub mod parse;
+pub mod report;
+
+pub use parse::ParseError;
+pub use report::Report;
+
+pub type Error = Box<dyn std::error::Error>;
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A puzzle that can be parsed from its input and answered in two parts.
+///
+/// Implemented by every day so that tooling (runners, benchmarks, regression
+/// checks) can treat each puzzle uniformly.
+pub trait Solution: Sized {
+	/// Day of the puzzle
+	const DAY: u32;
+
+	/// Answer to the first part of the puzzle
+	type Part1: fmt::Display;
+
+	/// Answer to the second part of the puzzle
+	type Part2: fmt::Display;
+
+	/// Parses the puzzle input.
+	fn parse(input: &str) -> std::result::Result<Self, ParseError>;
+
+	/// Solves the first part of the puzzle, failing if the answer can't be
+	/// worked out, e.g. because it's too large to hold.
+	fn part1(&self) -> Result<Self::Part1>;
+
+	/// Solves the second part of the puzzle, failing like
+	/// [`part1`](Self::part1).
+	fn part2(&self) -> Result<Self::Part2>;
+}
@@ -0,0 +1,401 @@
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A precise description of why puzzle input could not be parsed.
+///
+/// Lines and columns are 1-based; columns count characters, not bytes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+	/// The input had nothing to parse
+	Empty,
+	/// `text` starting at `line` and `column` is not what was `expected`
+	Invalid {
+		line: usize,
+		column: usize,
+		text: String,
+		expected: &'static str,
+	},
+	/// `line` ended at `column` before `expected` was found
+	Missing {
+		line: usize,
+		column: usize,
+		expected: &'static str,
+	},
+}
+
+impl ParseError {
+	/// Creates an error for `token`, a slice of `line`, on the first line.
+	pub fn invalid(line: &str, token: &str, expected: &'static str) -> Self {
+		ParseError::Invalid {
+			line: 1,
+			column: column(line, token),
+			text: token.to_owned(),
+			expected,
+		}
+	}
+
+	/// Creates an error for `line` ending before `expected` was found.
+	pub fn missing(line: &str, expected: &'static str) -> Self {
+		ParseError::Missing {
+			line: 1,
+			column: line.chars().count() + 1,
+			expected,
+		}
+	}
+
+	/// Moves an error produced by parsing a single line to `line`.
+	pub fn on_line(self, line: usize) -> Self {
+		match self {
+			ParseError::Empty => ParseError::Empty,
+			ParseError::Invalid {
+				column,
+				text,
+				expected,
+				..
+			} => ParseError::Invalid {
+				line,
+				column,
+				text,
+				expected,
+			},
+			ParseError::Missing {
+				column, expected, ..
+			} => ParseError::Missing {
+				line,
+				column,
+				expected,
+			},
+		}
+	}
+
+	/// Line the error occurred on, if any.
+	pub fn line(&self) -> Option<usize> {
+		match self {
+			ParseError::Empty => None,
+			ParseError::Invalid { line, .. } => Some(*line),
+			ParseError::Missing { line, .. } => Some(*line),
+		}
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ParseError::Empty => write!(f, "input is empty"),
+			ParseError::Invalid {
+				line,
+				column,
+				text,
+				expected,
+			} => write!(
+				f,
+				"line {}, column {}: expected {}, found `{}`",
+				line, column, expected, text
+			),
+			ParseError::Missing {
+				line,
+				column,
+				expected,
+			} => write!(
+				f,
+				"line {}, column {}: expected {}, found end of line",
+				line, column, expected
+			),
+		}
+	}
+}
+
+impl error::Error for ParseError {}
+
+// Character column of `token` within `line`, or the first column if `token`
+// is not a slice of `line`.
+fn column(line: &str, token: &str) -> usize {
+	let start = line.as_ptr() as usize;
+	let offset = (token.as_ptr() as usize).wrapping_sub(start);
+
+	line.get(..offset)
+		.map(|prefix| prefix.chars().count() + 1)
+		.unwrap_or(1)
+}
+
+/// Parses `token`, a slice of `line`, reporting failures against it.
+pub fn token<T: FromStr>(
+	line: &str,
+	token: &str,
+	expected: &'static str,
+) -> Result<T, ParseError> {
+	token
+		.parse()
+		.map_err(|_| ParseError::invalid(line, token, expected))
+}
+
+/// Walks a line from left to right, reporting errors against the full line.
+#[derive(Clone, Debug)]
+pub struct Cursor<'a> {
+	line: &'a str,
+	rest: &'a str,
+}
+
+impl<'a> Cursor<'a> {
+	/// Creates a cursor at the start of `line`.
+	pub fn new(line: &'a str) -> Self {
+		Self { line, rest: line }
+	}
+
+	/// The unconsumed remainder of the line
+	pub fn rest(&self) -> &'a str {
+		self.rest
+	}
+
+	/// Skips any whitespace.
+	pub fn skip_whitespace(&mut self) -> &mut Self {
+		self.rest = self.rest.trim_start();
+		self
+	}
+
+	/// Consumes `literal`.
+	pub fn literal(
+		&mut self,
+		literal: &str,
+		expected: &'static str,
+	) -> Result<&mut Self, ParseError> {
+		match self.rest.strip_prefix(literal) {
+			Some(rest) => {
+				self.rest = rest;
+
+				Ok(self)
+			}
+			None => Err(self.unexpected(expected)),
+		}
+	}
+
+	/// Consumes one or more characters matching `f`.
+	pub fn take_while<F>(
+		&mut self,
+		f: F,
+		expected: &'static str,
+	) -> Result<&'a str, ParseError>
+	where
+		F: Fn(char) -> bool,
+	{
+		let end = self.rest.find(|c| !f(c)).unwrap_or(self.rest.len());
+
+		if end == 0 {
+			return Err(self.unexpected(expected));
+		}
+
+		let (taken, rest) = self.rest.split_at(end);
+
+		self.rest = rest;
+
+		Ok(taken)
+	}
+
+	/// Consumes one or more characters matching `f` and parses them.
+	pub fn parse<T, F>(
+		&mut self,
+		f: F,
+		expected: &'static str,
+	) -> Result<T, ParseError>
+	where
+		T: FromStr,
+		F: Fn(char) -> bool,
+	{
+		let taken = self.take_while(f, expected)?;
+
+		token(self.line, taken, expected)
+	}
+
+	/// Consumes an optionally signed decimal integer.
+	pub fn integer<T: FromStr>(
+		&mut self,
+		expected: &'static str,
+	) -> Result<T, ParseError> {
+		let sign = match self.rest.chars().next() {
+			Some(c @ '-') | Some(c @ '+') => c.len_utf8(),
+			_ => 0,
+		};
+
+		let digits = self.rest[sign..]
+			.find(|c: char| !c.is_ascii_digit())
+			.unwrap_or(self.rest.len() - sign);
+
+		if digits == 0 {
+			return Err(self.unexpected(expected));
+		}
+
+		let (taken, rest) = self.rest.split_at(sign + digits);
+
+		self.rest = rest;
+
+		token(self.line, taken, expected)
+	}
+
+	/// Succeeds only if nothing but whitespace remains.
+	pub fn end(&self) -> Result<(), ParseError> {
+		if self.rest.trim().is_empty() {
+			Ok(())
+		} else {
+			Err(self.unexpected("end of line"))
+		}
+	}
+
+	/// Error for the upcoming word not being `expected`.
+	pub fn unexpected(&self, expected: &'static str) -> ParseError {
+		let rest = self.rest.trim_start();
+
+		if rest.is_empty() {
+			return ParseError::missing(self.line.trim_end(), expected);
+		}
+
+		let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+
+		ParseError::invalid(self.line, &rest[..end], expected)
+	}
+}
+
+/// Non-blank lines of `input` with their 1-based line numbers.
+pub fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+	input
+		.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.trim().is_empty())
+		.map(|(i, line)| (i + 1, line))
+}
+
+/// Parses every non-blank line of `input`, numbering any error with the line
+/// it occurred on. Fails with `ParseError::Empty` if there are no lines.
+pub fn parse_lines<T, F>(input: &str, mut f: F) -> Result<Vec<T>, ParseError>
+where
+	F: FnMut(&str) -> Result<T, ParseError>,
+{
+	let items = lines(input)
+		.map(|(number, line)| f(line).map_err(|err| err.on_line(number)))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	if items.is_empty() {
+		return Err(ParseError::Empty);
+	}
+
+	Ok(items)
+}
+
+/// Parses the only non-blank line of `input`, numbering any error with the
+/// line it occurred on. Fails with `ParseError::Empty` if there is no line.
+pub fn single_line<T, F>(input: &str, f: F) -> Result<T, ParseError>
+where
+	F: FnOnce(&str) -> Result<T, ParseError>,
+{
+	let mut lines = lines(input);
+	let (number, line) = lines.next().ok_or(ParseError::Empty)?;
+	let item = f(line).map_err(|err| err.on_line(number))?;
+
+	if let Some((number, line)) = lines.next() {
+		return Err(ParseError::invalid(line, line.trim(), "end of input")
+			.on_line(number));
+	}
+
+	Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn invalid() {
+		let line = "  #1 @ 1,x: 4x4";
+		let err = ParseError::invalid(line, &line[9..10], "a number");
+
+		assert_eq!(
+			err.on_line(3).to_string(),
+			"line 3, column 10: expected a number, found `x`"
+		);
+	}
+
+	#[test]
+	fn parse_lines() {
+		let nums = super::parse_lines("1\n\n 2 \n", |line| {
+			token::<u32>(line, line.trim(), "a number")
+		});
+
+		assert_eq!(nums, Ok(vec![1, 2]));
+
+		let err = super::parse_lines("1\n\n2\né3", |line| {
+			token::<u32>(line, line.trim(), "a number")
+		});
+
+		assert_eq!(
+			err,
+			Err(ParseError::Invalid {
+				line: 4,
+				column: 1,
+				text: "é3".to_owned(),
+				expected: "a number",
+			})
+		);
+
+		assert_eq!(
+			super::parse_lines("\n \n", |line| Ok(line.to_owned())),
+			Err(ParseError::Empty)
+		);
+	}
+
+	#[test]
+	fn single_line() {
+		let parse = |input| {
+			super::single_line(input, |line| {
+				token::<u32>(line, line.trim(), "a number")
+			})
+		};
+
+		assert_eq!(parse("\n 18\n"), Ok(18));
+		assert_eq!(parse(""), Err(ParseError::Empty));
+		assert_eq!(
+			parse("18\n\n 42"),
+			Err(ParseError::Invalid {
+				line: 3,
+				column: 2,
+				text: "42".to_owned(),
+				expected: "end of input",
+			})
+		);
+	}
+
+	#[test]
+	fn cursor() {
+		let line = "#1 @ 861,-330: 20x10";
+		let mut cursor = Cursor::new(line);
+
+		cursor.literal("#", "`#`").unwrap();
+		assert_eq!(cursor.integer::<u32>("an id"), Ok(1));
+		cursor.literal(" @ ", "` @ `").unwrap();
+		assert_eq!(cursor.integer::<i32>("a number"), Ok(861));
+		cursor.literal(",", "`,`").unwrap();
+		assert_eq!(cursor.integer::<i32>("a number"), Ok(-330));
+
+		assert_eq!(
+			cursor.literal(",", "`,`").unwrap_err().to_string(),
+			"line 1, column 14: expected `,`, found `:`"
+		);
+
+		cursor.literal(": ", "`: `").unwrap();
+		assert_eq!(cursor.take_while(char::is_numeric, "a width"), Ok("20"));
+		cursor.literal("x", "`x`").unwrap();
+		assert_eq!(
+			cursor.parse::<u32, _>(char::is_numeric, "a height"),
+			Ok(10)
+		);
+		assert_eq!(cursor.end(), Ok(()));
+
+		assert_eq!(
+			cursor.integer::<u32>("a number"),
+			Err(ParseError::Missing {
+				line: 1,
+				column: 21,
+				expected: "a number",
+			})
+		);
+	}
+}
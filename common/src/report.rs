@@ -0,0 +1,256 @@
+use std::fmt::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::{alloc, ParseError, Solution};
+
+/// Time spent parsing the input and solving each part.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Timings {
+	pub parse: Duration,
+	pub part1: Duration,
+	pub part2: Duration,
+}
+
+/// Most bytes allocated above the starting heap usage while parsing the
+/// input and solving each part.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PeakHeap {
+	pub parse: usize,
+	pub part1: usize,
+	pub part2: usize,
+}
+
+/// Answers to both parts of a day along with how long each step took.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+	pub day: u32,
+	/// The answer, or why it couldn't be worked out
+	pub part1: Result<String, String>,
+	pub part2: Result<String, String>,
+	pub timings: Timings,
+	/// Only known when `alloc::Counting` is the global allocator
+	pub peak_heap: Option<PeakHeap>,
+}
+
+/// Runs `f`, returning its result, how long it took and the most bytes it
+/// had allocated at once. Heap usage is always zero unless
+/// `alloc::Counting` is the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration, usize) {
+	let baseline = alloc::reset_peak();
+	let start = Instant::now();
+	let result = f();
+	let time = start.elapsed();
+
+	(result, time, alloc::peak().saturating_sub(baseline))
+}
+
+impl Report {
+	/// Parses `input` and solves both parts, measuring each step.
+	pub fn run<S: Solution>(input: &str) -> Result<Self, ParseError> {
+		Self::run_with(input, S::part1, S::part2)
+	}
+
+	/// Parses `input` and solves each part with `part1` and `part2` in
+	/// place of the day's own, e.g. to apply options given on the command
+	/// line.
+	pub fn run_with<S, A, B>(
+		input: &str,
+		part1: impl FnOnce(&S) -> crate::Result<A>,
+		part2: impl FnOnce(&S) -> crate::Result<B>,
+	) -> Result<Self, ParseError>
+	where
+		S: Solution,
+		A: fmt::Display,
+		B: fmt::Display,
+	{
+		let (day, parse, parse_heap) = measure(|| S::parse(input));
+		let day = day?;
+		let (part1, part1_time, part1_heap) = measure(|| part1(&day));
+		let (part2, part2_time, part2_heap) = measure(|| part2(&day));
+		let peak_heap = if alloc::installed() {
+			Some(PeakHeap {
+				parse: parse_heap,
+				part1: part1_heap,
+				part2: part2_heap,
+			})
+		} else {
+			None
+		};
+
+		Ok(Report {
+			day: S::DAY,
+			part1: answer(part1),
+			part2: answer(part2),
+			timings: Timings {
+				parse,
+				part1: part1_time,
+				part2: part2_time,
+			},
+			peak_heap,
+		})
+	}
+
+	/// Whether either part failed.
+	pub fn failed(&self) -> bool {
+		self.part1.is_err() || self.part2.is_err()
+	}
+
+	/// Serializes the report as a single line of JSON. Answers are always
+	/// strings, or `{"error":...}` for a part that failed, timings are in
+	/// milliseconds and peak heap usage is in bytes (`null` if unknown), e.g.
+	/// `{"day":7,"part1":"CABDFE","part2":"253","timings":{"parse":0.012,...},"peak_heap":{"parse":2048,...}}`.
+	pub fn to_json(&self) -> String {
+		let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+		let peak_heap = match &self.peak_heap {
+			Some(heap) => format!(
+				r#"{{"parse":{},"part1":{},"part2":{}}}"#,
+				heap.parse, heap.part1, heap.part2
+			),
+			None => "null".to_owned(),
+		};
+
+		format!(
+			r#"{{"day":{},"part1":{},"part2":{},"timings":{{"parse":{:.3},"part1":{:.3},"part2":{:.3}}},"peak_heap":{}}}"#,
+			self.day,
+			answer_json(&self.part1),
+			answer_json(&self.part2),
+			ms(self.timings.parse),
+			ms(self.timings.part1),
+			ms(self.timings.part2),
+			peak_heap,
+		)
+	}
+}
+
+// An answer as text, or why it couldn't be worked out
+fn answer(answer: crate::Result<impl fmt::Display>) -> Result<String, String> {
+	answer
+		.map(|answer| answer.to_string())
+		.map_err(|err| err.to_string())
+}
+
+// An answer as a JSON string, or an object holding the error
+fn answer_json(answer: &Result<String, String>) -> String {
+	match answer {
+		Ok(answer) => json_string(answer),
+		Err(err) => format!(r#"{{"error":{}}}"#, json_string(err)),
+	}
+}
+
+/// Quotes and escapes `s` as a JSON string.
+pub fn json_string(s: &str) -> String {
+	let mut json = String::with_capacity(s.len() + 2);
+
+	json.push('"');
+
+	for c in s.chars() {
+		match c {
+			'"' => json.push_str("\\\""),
+			'\\' => json.push_str("\\\\"),
+			'\n' => json.push_str("\\n"),
+			'\r' => json.push_str("\\r"),
+			'\t' => json.push_str("\\t"),
+			c if c.is_control() => {
+				let _ = write!(json, "\\u{:04x}", c as u32);
+			}
+			c => json.push(c),
+		}
+	}
+
+	json.push('"');
+	json
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn to_json() {
+		let report = Report {
+			day: 10,
+			part1: Ok("#.\n\"#\"".to_owned()),
+			part2: Ok("3".to_owned()),
+			timings: Timings {
+				parse: Duration::from_micros(1500),
+				part1: Duration::from_millis(20),
+				part2: Duration::from_nanos(1),
+			},
+			peak_heap: None,
+		};
+
+		assert_eq!(
+			report.to_json(),
+			r##"{"day":10,"part1":"#.\n\"#\"","part2":"3","timings":{"parse":1.500,"part1":20.000,"part2":0.000},"peak_heap":null}"##
+		);
+
+		let report = Report {
+			peak_heap: Some(PeakHeap {
+				parse: 2048,
+				part1: 0,
+				part2: 1,
+			}),
+			..report
+		};
+
+		assert!(report
+			.to_json()
+			.ends_with(r#""peak_heap":{"parse":2048,"part1":0,"part2":1}}"#));
+
+		let report = Report {
+			part2: Err("too \"large\"".to_owned()),
+			..report
+		};
+
+		assert!(report.failed());
+		assert!(report
+			.to_json()
+			.contains(r#""part2":{"error":"too \"large\""},"timings""#));
+	}
+
+	// Counts the words of its input
+	struct Words(Vec<String>);
+
+	impl Solution for Words {
+		const DAY: u32 = 0;
+
+		type Part1 = usize;
+		type Part2 = usize;
+
+		fn parse(input: &str) -> Result<Self, ParseError> {
+			Ok(Words(input.split_whitespace().map(str::to_owned).collect()))
+		}
+
+		fn part1(&self) -> crate::Result<usize> {
+			Ok(self.0.len())
+		}
+
+		fn part2(&self) -> crate::Result<usize> {
+			self.0
+				.iter()
+				.try_fold(0usize, |sum, word| sum.checked_add(word.len()))
+				.ok_or_else(|| "too many letters".into())
+		}
+	}
+
+	#[test]
+	fn run_with() {
+		let report = Report::run::<Words>("a bb ccc").unwrap();
+
+		assert_eq!(report.part1, Ok("3".to_owned()));
+		assert_eq!(report.part2, Ok("6".to_owned()));
+
+		let report = Report::run_with(
+			"a bb ccc",
+			|words: &Words| Ok(words.0.join("-")),
+			|words: &Words| {
+				Err::<usize, _>(format!("{} words", words.0.len()).into())
+			},
+		)
+		.unwrap();
+
+		assert_eq!(report.day, 0);
+		assert_eq!(report.part1, Ok("a-bb-ccc".to_owned()));
+		assert_eq!(report.part2, Err("3 words".to_owned()));
+	}
+}
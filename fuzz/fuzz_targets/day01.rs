@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc_fuzz::text;
+use common::Solution;
+use day01::Day01;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	if let Some(Ok(day)) = text(data).map(Day01::parse) {
+		let _ = day.part1();
+		let _ = day.part2();
+	}
+});
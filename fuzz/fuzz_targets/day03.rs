@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc_fuzz::text;
+use common::Solution;
+use day03::Day03;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	if let Some(Ok(day)) = text(data).map(Day03::parse) {
+		day.part1().unwrap();
+		day.part2().unwrap();
+	}
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc_fuzz::text;
+use common::Solution;
+use day05::Day05;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	if let Some(Ok(day)) = text(data).map(Day05::parse) {
+		day.part1().unwrap();
+		day.part2().unwrap();
+	}
+});
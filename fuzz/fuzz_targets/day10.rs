@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc_fuzz::text;
+use common::Solution;
+use day10::Day10;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	if let Some(Ok(day)) = text(data).map(Day10::parse) {
+		// Parsing rejects skies too spread out to draw
+		day.part1().unwrap();
+		day.part2().unwrap();
+	}
+});
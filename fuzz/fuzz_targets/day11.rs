@@ -0,0 +1,13 @@
+#![no_main]
+
+use aoc_fuzz::text;
+use common::Solution;
+use day11::Day11;
+use libfuzzer_sys::fuzz_target;
+
+// Part 2 checks every square on the grid, which is far too slow to fuzz
+fuzz_target!(|data: &[u8]| {
+	if let Some(Ok(day)) = text(data).map(Day11::parse) {
+		day.part1().unwrap();
+	}
+});
@@ -0,0 +1,8 @@
+//! Helpers shared by the fuzz targets
+
+use std::str;
+
+/// Fuzz input as text, if it's valid UTF-8.
+pub fn text(data: &[u8]) -> Option<&str> {
+	str::from_utf8(data).ok()
+}
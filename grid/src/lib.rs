@@ -0,0 +1,399 @@
+//! A fixed size two dimensional grid shared by the grid based days
+//!
+//! Cells are addressed by `(x, y)`, with `x` the column and `y` the row,
+//! both counting from zero at the top left.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A grid of cells stored row by row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+	width: usize,
+	height: usize,
+	cells: Vec<T>,
+}
+
+// Offsets to the four cells sharing an edge, then the four diagonals
+const NEIGHBOURS: [(isize, isize); 8] = [
+	(0, -1),
+	(-1, 0),
+	(1, 0),
+	(0, 1),
+	(-1, -1),
+	(1, -1),
+	(-1, 1),
+	(1, 1),
+];
+
+impl<T> Grid<T> {
+	/// Creates a grid with every cell set to `value`.
+	pub fn new(width: usize, height: usize, value: T) -> Self
+	where
+		T: Clone,
+	{
+		Self {
+			width,
+			height,
+			cells: vec![value; width * height],
+		}
+	}
+
+	/// Creates a grid with each cell set to `f(x, y)`.
+	pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+	where
+		F: FnMut(usize, usize) -> T,
+	{
+		let cells = (0..height)
+			.flat_map(|y| (0..width).map(move |x| (x, y)))
+			.map(|(x, y)| f(x, y))
+			.collect();
+
+		Self {
+			width,
+			height,
+			cells,
+		}
+	}
+
+	/// Creates a grid from its rows, padding any row shorter than the
+	/// longest with `fill`.
+	pub fn from_rows(rows: Vec<Vec<T>>, fill: T) -> Self
+	where
+		T: Clone,
+	{
+		let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+		let height = rows.len();
+		let mut cells = Vec::with_capacity(width * height);
+
+		for mut row in rows {
+			row.resize(width, fill.clone());
+			cells.extend(row);
+		}
+
+		Self {
+			width,
+			height,
+			cells,
+		}
+	}
+
+	/// Grid of the same size with each cell set to `f` of the cell here.
+	pub fn map<U, F>(&self, f: F) -> Grid<U>
+	where
+		F: FnMut(&T) -> U,
+	{
+		Grid {
+			width: self.width,
+			height: self.height,
+			cells: self.cells.iter().map(f).collect(),
+		}
+	}
+
+	/// Number of columns.
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	/// Number of rows.
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	fn offset(&self, x: usize, y: usize) -> Option<usize> {
+		if x < self.width && y < self.height {
+			Some(y * self.width + x)
+		} else {
+			None
+		}
+	}
+
+	/// Cell at `(x, y)`, if it's on the grid.
+	pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+		self.offset(x, y).map(|i| &self.cells[i])
+	}
+
+	/// Mutable cell at `(x, y)`, if it's on the grid.
+	pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+		let i = self.offset(x, y)?;
+
+		Some(&mut self.cells[i])
+	}
+
+	/// Every row, top to bottom.
+	pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+		let width = self.width;
+
+		(0..self.height)
+			.map(move |y| &self.cells[(y * width)..((y + 1) * width)])
+	}
+
+	/// Every cell with its `(x, y)` location, in reading order.
+	pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+		let width = self.width;
+
+		self.cells
+			.iter()
+			.enumerate()
+			.map(move |(i, cell)| ((i % width, i / width), cell))
+	}
+
+	/// Every mutable cell with its `(x, y)` location, in reading order.
+	pub fn iter_mut(
+		&mut self,
+	) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+		let width = self.width;
+
+		self.cells
+			.iter_mut()
+			.enumerate()
+			.map(move |(i, cell)| ((i % width, i / width), cell))
+	}
+
+	/// Whether `(x, y)` is in the first or last row or column.
+	pub fn is_edge(&self, x: usize, y: usize) -> bool {
+		x == 0 || y == 0 || x + 1 == self.width || y + 1 == self.height
+	}
+
+	fn neighbours(
+		&self,
+		x: usize,
+		y: usize,
+		n: usize,
+	) -> impl Iterator<Item = (usize, usize)> + '_ {
+		NEIGHBOURS[..n].iter().filter_map(move |&(dx, dy)| {
+			let x = (x as isize).checked_add(dx)?;
+			let y = (y as isize).checked_add(dy)?;
+
+			if x < 0 || y < 0 {
+				return None;
+			}
+
+			let (x, y) = (x as usize, y as usize);
+
+			self.offset(x, y).map(|_| (x, y))
+		})
+	}
+
+	/// Locations of the up to four cells sharing an edge with `(x, y)`.
+	pub fn neighbours4(
+		&self,
+		x: usize,
+		y: usize,
+	) -> impl Iterator<Item = (usize, usize)> + '_ {
+		self.neighbours(x, y, 4)
+	}
+
+	/// Locations of the up to eight cells sharing an edge or a corner with
+	/// `(x, y)`.
+	pub fn neighbours8(
+		&self,
+		x: usize,
+		y: usize,
+	) -> impl Iterator<Item = (usize, usize)> + '_ {
+		self.neighbours(x, y, 8)
+	}
+
+	/// The `width`x`height` rectangle with its top-left cell at `(x, y)`,
+	/// if it fits entirely on the grid.
+	pub fn view(
+		&self,
+		x: usize,
+		y: usize,
+		width: usize,
+		height: usize,
+	) -> Option<View<'_, T>> {
+		let fits = |start: usize, len: usize, max: usize| {
+			start.checked_add(len).is_some_and(|end| end <= max)
+		};
+
+		if fits(x, width, self.width) && fits(y, height, self.height) {
+			Some(View {
+				grid: self,
+				x,
+				y,
+				width,
+				height,
+			})
+		} else {
+			None
+		}
+	}
+
+	/// Draws each row on its own line, with `f` drawing each cell.
+	pub fn render<D, F>(&self, mut f: F) -> String
+	where
+		D: fmt::Display,
+		F: FnMut(&T) -> D,
+	{
+		let lines = self
+			.rows()
+			.map(|row| row.iter().map(|cell| f(cell).to_string()).collect())
+			.collect::<Vec<String>>();
+
+		lines.join("\n")
+	}
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+	type Output = T;
+
+	fn index(&self, (x, y): (usize, usize)) -> &T {
+		self.get(x, y).unwrap_or_else(|| {
+			panic!(
+				"({}, {}) is outside the {}x{} grid",
+				x, y, self.width, self.height
+			)
+		})
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+	fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+		let (width, height) = (self.width, self.height);
+
+		self.get_mut(x, y).unwrap_or_else(|| {
+			panic!("({}, {}) is outside the {}x{} grid", x, y, width, height)
+		})
+	}
+}
+
+/// A rectangle of cells within a grid.
+#[derive(Clone, Copy, Debug)]
+pub struct View<'a, T> {
+	grid: &'a Grid<T>,
+	x: usize,
+	y: usize,
+	width: usize,
+	height: usize,
+}
+
+impl<'a, T> View<'a, T> {
+	/// Number of columns.
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	/// Number of rows.
+	pub fn height(&self) -> usize {
+		self.height
+	}
+
+	/// Cell at `(x, y)` relative to the top-left of the view, if it's in
+	/// the view.
+	pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+		if x < self.width && y < self.height {
+			self.grid.get(self.x + x, self.y + y)
+		} else {
+			None
+		}
+	}
+
+	/// Every row of the view, top to bottom.
+	pub fn rows(&self) -> impl Iterator<Item = &'a [T]> {
+		let View {
+			grid,
+			x,
+			y,
+			width,
+			height,
+		} = *self;
+
+		grid.rows()
+			.skip(y)
+			.take(height)
+			.map(move |row| &row[x..(x + width)])
+	}
+
+	/// Every cell in the view, in reading order.
+	pub fn cells(&self) -> impl Iterator<Item = &'a T> {
+		self.rows().flatten()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn numbered(width: usize, height: usize) -> Grid<usize> {
+		Grid::from_fn(width, height, |x, y| y * 10 + x)
+	}
+
+	#[test]
+	fn access() {
+		let mut grid = numbered(3, 2);
+
+		assert_eq!(grid.get(2, 1), Some(&12));
+		assert_eq!(grid.get(3, 0), None);
+		assert_eq!(grid.get(0, 2), None);
+
+		grid[(1, 0)] = 99;
+
+		assert_eq!(grid[(1, 0)], 99);
+		assert_eq!(
+			grid.rows().collect::<Vec<_>>(),
+			[&[0, 99, 2], &[10, 11, 12]]
+		);
+	}
+
+	#[test]
+	#[should_panic(expected = "(3, 0) is outside the 3x2 grid")]
+	fn index_out_of_bounds() {
+		let _cell = numbered(3, 2)[(3, 0)];
+	}
+
+	#[test]
+	fn from_rows() {
+		let grid = Grid::from_rows(vec![vec![1], vec![], vec![1, 2, 3]], 0);
+
+		assert_eq!((grid.width(), grid.height()), (3, 3));
+		assert_eq!(grid.render(|&n| n), "100\n000\n123");
+		assert_eq!(Grid::from_rows(Vec::<Vec<u8>>::new(), 0).rows().count(), 0);
+		assert_eq!(Grid::from_rows(vec![vec![0u8; 0]; 2], 0).rows().count(), 2);
+	}
+
+	#[test]
+	fn neighbours() {
+		let grid = numbered(3, 3);
+
+		assert_eq!(
+			grid.neighbours4(1, 1).collect::<Vec<_>>(),
+			[(1, 0), (0, 1), (2, 1), (1, 2)]
+		);
+		assert_eq!(
+			grid.neighbours4(0, 0).collect::<Vec<_>>(),
+			[(1, 0), (0, 1)]
+		);
+		assert_eq!(grid.neighbours8(1, 1).count(), 8);
+		assert_eq!(
+			grid.neighbours8(2, 2).collect::<Vec<_>>(),
+			[(2, 1), (1, 2), (1, 1)]
+		);
+	}
+
+	#[test]
+	fn view() {
+		let grid = numbered(4, 3);
+		let view = grid.view(1, 1, 2, 2).unwrap();
+
+		assert_eq!(view.get(1, 0), Some(&12));
+		assert_eq!(view.get(2, 0), None);
+		assert_eq!(view.cells().copied().collect::<Vec<_>>(), [11, 12, 21, 22]);
+		assert!(grid.view(3, 0, 2, 1).is_none());
+		assert!(grid.view(0, 0, 4, 3).is_some());
+		assert!(grid.view(usize::MAX, 0, 2, 1).is_none());
+	}
+
+	#[test]
+	fn iter() {
+		let grid = numbered(2, 2);
+
+		assert_eq!(
+			grid.iter().map(|(xy, &n)| (xy, n)).collect::<Vec<_>>(),
+			[((0, 0), 0), ((1, 0), 1), ((0, 1), 10), ((1, 1), 11)]
+		);
+		assert_eq!(grid.map(|n| n % 10).render(|&n| n), "01\n01");
+		assert!(grid.is_edge(1, 0));
+		assert!(!numbered(3, 3).is_edge(1, 1));
+	}
+}
@@ -0,0 +1,72 @@
+//! Random frequency changes with known answers
+
+use std::collections::HashSet;
+
+use common::generate::{Generated, Rng};
+
+/// Generates `n` (at least 3) frequency changes. Every frequency reached
+/// before the last change is distinct, and the last change returns to an
+/// earlier one, so the first repeat is the final frequency.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<i64, i64> {
+	let n = n.max(3);
+	let limit = 100 * n as i64;
+	let mut seen = HashSet::new();
+	let mut frequencies = Vec::with_capacity(n);
+
+	seen.insert(0);
+
+	while frequencies.len() < n - 1 {
+		let frequency = rng.range(-limit, limit);
+
+		if seen.insert(frequency) {
+			frequencies.push(frequency);
+		}
+	}
+
+	// Skip the last frequency so that no change is zero
+	let repeat = frequencies[rng.below(n - 2)];
+
+	frequencies.push(repeat);
+
+	let input = frequencies
+		.iter()
+		.scan(0, |prev, &frequency| {
+			let change = frequency - *prev;
+
+			*prev = frequency;
+
+			Some(format!("{:+}\n", change))
+		})
+		.collect();
+
+	Generated {
+		input,
+		part1: Some(repeat),
+		part2: Some(repeat),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day01;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(1);
+
+		for n in &[3, 10, 1000] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day01 = generated.input.parse().unwrap();
+
+			let answer = |n: Option<i64>| n.map(|n| n.to_string());
+
+			assert_eq!(
+				day.part1().ok().map(|n| n.to_string()),
+				answer(generated.part1)
+			);
+			assert_eq!(day.part2().ok(), answer(generated.part2));
+		}
+	}
+}
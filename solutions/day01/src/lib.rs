@@ -0,0 +1,911 @@
+//! Day 1: Chronal Calibration
+
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::error;
+use std::fmt;
+use std::io::BufRead;
+use std::mem;
+use std::str::FromStr;
+
+use common::parse::{self, ParseError};
+use common::Solution;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// A frequency, or a change to one. Frequencies are `i64`s unless the
+/// `bigint` feature is enabled, in which case they're unbounded.
+#[cfg(not(feature = "bigint"))]
+pub type Frequency = i64;
+#[cfg(feature = "bigint")]
+pub type Frequency = num_bigint::BigInt;
+
+/// An integer wide enough for the difference between any two frequencies,
+/// and so for the number of passes before any repeat.
+#[cfg(not(feature = "bigint"))]
+pub type Wide = i128;
+#[cfg(feature = "bigint")]
+pub type Wide = num_bigint::BigInt;
+
+#[cfg(not(feature = "bigint"))]
+fn add(frequency: &Frequency, change: &Frequency) -> Option<Frequency> {
+	frequency.checked_add(*change)
+}
+
+#[cfg(feature = "bigint")]
+fn add(frequency: &Frequency, change: &Frequency) -> Option<Frequency> {
+	Some(frequency + change)
+}
+
+#[cfg(not(feature = "bigint"))]
+fn widen(frequency: &Frequency) -> Wide {
+	Wide::from(*frequency)
+}
+
+#[cfg(feature = "bigint")]
+fn widen(frequency: &Frequency) -> Wide {
+	frequency.clone()
+}
+
+// First change, counting from 1, to take the frequency out of range when
+// every pass shifts the frequencies before each change from `starts` by
+// `drift`
+#[cfg(not(feature = "bigint"))]
+fn first_overflow(starts: &[Frequency], drift: &Wide) -> Option<Wide> {
+	let len = starts.len() as Wide;
+
+	starts
+		.iter()
+		.enumerate()
+		.filter_map(|(k, &start)| {
+			let start = Wide::from(start);
+			let room = if *drift > 0 {
+				Wide::from(Frequency::MAX) - start
+			} else {
+				start - Wide::from(Frequency::MIN)
+			};
+			let passes = room / drift.abs() + 1;
+
+			passes.checked_mul(len)?.checked_add(k as Wide)
+		})
+		.min()
+}
+
+#[cfg(feature = "bigint")]
+fn first_overflow(_: &[Frequency], _: &Wide) -> Option<Wide> {
+	None
+}
+
+// Remainder of `n` divided by `divisor`, from zero up to the divisor's size
+fn residue(n: &Wide, divisor: &Wide) -> Wide {
+	let zero = Wide::default();
+	let remainder = n % divisor;
+
+	match (remainder < zero, divisor < &zero) {
+		(false, _) => remainder,
+		(true, false) => remainder + divisor,
+		(true, true) => remainder - divisor,
+	}
+}
+
+/// The frequency went out of range on the `change`th change (counting from
+/// 1). This can't happen with the `bigint` feature enabled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Overflow {
+	pub change: usize,
+}
+
+impl fmt::Display for Overflow {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "frequency out of range after change {}", self.change)
+	}
+}
+
+impl error::Error for Overflow {}
+
+/// A list of frequency changes, one per input line (e.g. `+1`, `-2`).
+#[derive(Debug, PartialEq)]
+pub struct Day01 {
+	nums: Vec<Frequency>,
+	// Line each change was read from
+	lines: Vec<usize>,
+}
+
+/// The change that reached a frequency: the `line` it was read from and the
+/// `pass` through the changes, both counting from 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Position {
+	pub line: usize,
+	pub pass: Wide,
+}
+
+impl fmt::Display for Position {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "line {} of pass {}", self.line, self.pass)
+	}
+}
+
+impl Position {
+	/// Encodes the position as a JSON object.
+	pub fn to_json(&self) -> String {
+		format!(r#"{{"line":{},"pass":{}}}"#, self.line, self.pass)
+	}
+}
+
+/// A frequency reached twice while cycling through the changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Repeat {
+	pub frequency: Frequency,
+	/// Where the frequency was reached again
+	pub at: Position,
+	/// Where the frequency was first reached, or `None` if it's the starting
+	/// frequency of zero
+	pub first_seen: Option<Position>,
+}
+
+/// Writes the frequency and where it was reached, e.g. `10 on line 2 of
+/// pass 2, first seen on line 4 of pass 1`.
+impl fmt::Display for Repeat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} on {}, first seen ", self.frequency, self.at)?;
+
+		match &self.first_seen {
+			Some(position) => write!(f, "on {}", position),
+			None => write!(f, "at the start"),
+		}
+	}
+}
+
+impl Repeat {
+	/// Encodes the repeat as a JSON object, with a `first_seen` of `null`
+	/// for the starting frequency.
+	pub fn to_json(&self) -> String {
+		format!(
+			r#"{{"frequency":{},"at":{},"first_seen":{}}}"#,
+			self.frequency,
+			self.at.to_json(),
+			self.first_seen
+				.as_ref()
+				.map_or_else(|| "null".to_owned(), Position::to_json)
+		)
+	}
+}
+
+// A frequency change on a line of its own, e.g. `+1`
+fn change(line: &str) -> Result<Frequency, ParseError> {
+	parse::token(line, line.trim(), "a frequency change")
+}
+
+impl FromStr for Day01 {
+	type Err = ParseError;
+
+	/// Parses one change per line. Changes that take the frequency out of
+	/// range are only reported when solving, as the first repeat may come
+	/// before them.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let nums = parse::parse_lines(s, change)?;
+		let lines = parse::lines(s).map(|(number, _)| number).collect();
+
+		Ok(Day01 { nums, lines })
+	}
+}
+
+/// Writes one change per line, always signed, e.g. `+1`.
+impl fmt::Display for Day01 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, change) in self.nums.iter().enumerate() {
+			if i > 0 {
+				writeln!(f)?;
+			}
+
+			write!(f, "{:+}", change)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Frequency changes read from `reader` a line at a time, skipping blank
+/// lines. Parse errors are numbered with the line they occurred on.
+pub fn read_changes(
+	reader: impl BufRead,
+) -> impl Iterator<Item = common::Result<Frequency>> {
+	reader
+		.lines()
+		.enumerate()
+		.filter_map(|(i, line)| match line {
+			Ok(line) if line.trim().is_empty() => None,
+			Ok(line) => {
+				Some(change(&line).map_err(|err| err.on_line(i + 1).into()))
+			}
+			Err(err) => Some(Err(err.into())),
+		})
+}
+
+/// Applies frequency changes as they arrive, e.g. from a live feed of device
+/// readings, without holding on to the changes themselves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Calibrator {
+	frequency: Frequency,
+	changes: usize,
+	// Every frequency reached so far, until one is reached twice
+	visited: HashSet<Frequency>,
+	first_repeat: Option<Frequency>,
+}
+
+impl Calibrator {
+	/// Creates a calibrator at frequency zero.
+	pub fn new() -> Self {
+		let mut visited = HashSet::new();
+
+		visited.insert(Frequency::default());
+
+		Self {
+			frequency: Frequency::default(),
+			changes: 0,
+			visited,
+			first_repeat: None,
+		}
+	}
+
+	/// Applies a change, returning the new frequency if it's the first to be
+	/// reached twice. A change that takes the frequency out of range is
+	/// rejected and leaves the calibrator as it was.
+	#[cfg_attr(not(feature = "bigint"), allow(clippy::clone_on_copy))]
+	pub fn push(
+		&mut self,
+		change: Frequency,
+	) -> Result<Option<Frequency>, Overflow> {
+		self.frequency = add(&self.frequency, &change).ok_or(Overflow {
+			change: self.changes + 1,
+		})?;
+		self.changes += 1;
+
+		if self.first_repeat.is_some()
+			|| self.visited.insert(self.frequency.clone())
+		{
+			return Ok(None);
+		}
+
+		// Nothing else needs to be remembered once there's a repeat
+		self.visited = HashSet::new();
+		self.first_repeat = Some(self.frequency.clone());
+
+		Ok(self.first_repeat.clone())
+	}
+
+	/// Applies every change in turn, stopping at the first that takes the
+	/// frequency out of range.
+	pub fn push_all(
+		&mut self,
+		changes: impl IntoIterator<Item = Frequency>,
+	) -> Result<(), Overflow> {
+		for change in changes {
+			self.push(change)?;
+		}
+
+		Ok(())
+	}
+
+	/// Applies every change read from `reader`, stopping at the first that
+	/// can't be read or takes the frequency out of range.
+	pub fn read(&mut self, reader: impl BufRead) -> common::Result<()> {
+		for change in read_changes(reader) {
+			self.push(change?)?;
+		}
+
+		Ok(())
+	}
+
+	/// Frequency after every change so far.
+	#[cfg_attr(not(feature = "bigint"), allow(clippy::clone_on_copy))]
+	pub fn frequency(&self) -> Frequency {
+		self.frequency.clone()
+	}
+
+	/// First frequency reached twice so far, if any.
+	#[cfg_attr(not(feature = "bigint"), allow(clippy::clone_on_copy))]
+	pub fn first_repeat(&self) -> Option<Frequency> {
+		self.first_repeat.clone()
+	}
+}
+
+impl Default for Calibrator {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Day01 {
+	/// Creates a calibration from a list of frequency changes, one per line.
+	pub fn new(nums: Vec<Frequency>) -> Day01 {
+		let lines = (1..=nums.len()).collect();
+
+		Day01 { nums, lines }
+	}
+
+	// Where the frequency before change `i` of pass `passes` (both counting
+	// from 0) was reached, or `None` for the starting frequency
+	fn position(&self, passes: Wide, i: usize) -> Option<Position> {
+		let one = Wide::from(1u8);
+
+		match i.checked_sub(1) {
+			Some(prev) => Some(Position {
+				line: self.lines[prev],
+				pass: passes + one,
+			}),
+			None if passes == Wide::default() => None,
+			None => Some(Position {
+				line: *self.lines.last()?,
+				pass: passes,
+			}),
+		}
+	}
+
+	// `frequency` reached again before change `i` of pass `passes`, first
+	// reached before change `j` of the first pass
+	fn repeat_at(
+		&self,
+		frequency: Frequency,
+		(passes, i): (Wide, usize),
+		(first_passes, j): (Wide, usize),
+	) -> Repeat {
+		Repeat {
+			frequency,
+			// Nothing can be reached again before the first change
+			at: self.position(passes, i).unwrap(),
+			first_seen: self.position(first_passes, j),
+		}
+	}
+
+	/// Resulting frequency after applying every change once (part 1).
+	pub fn frequency(&self) -> Result<Frequency, Overflow> {
+		self.nums.iter().enumerate().try_fold(
+			Frequency::default(),
+			|frequency, (i, change)| {
+				add(&frequency, change).ok_or(Overflow { change: i + 1 })
+			},
+		)
+	}
+
+	/// First frequency reached twice while cycling through the changes
+	/// (part 2), or `None` if no frequency is ever reached twice.
+	pub fn first_repeat(&self) -> Result<Option<Frequency>, Overflow> {
+		Ok(self.repeat()?.map(|repeat| repeat.frequency))
+	}
+
+	/// First frequency reached twice while cycling through the changes,
+	/// along with where it was reached both times.
+	///
+	/// Rather than cycling, this works from the frequencies reached in the
+	/// first pass. Each later pass shifts every one of them by the total
+	/// drift, so a frequency can only come back around to another with the
+	/// same remainder modulo the drift, after as many passes as the drift
+	/// divides their difference. The frequencies in between are never
+	/// added up, but this still fails if any of them would be out of range
+	/// before anything repeats.
+	#[cfg_attr(
+		not(feature = "bigint"),
+		allow(clippy::clone_on_copy, clippy::op_ref)
+	)]
+	pub fn repeat(&self) -> Result<Option<Repeat>, Overflow> {
+		// Frequency before each change of the first pass, up to any change
+		// that goes out of range
+		let mut starts = Vec::with_capacity(self.nums.len());
+		let mut frequency = Frequency::default();
+		let mut overflow = None;
+
+		for (i, change) in self.nums.iter().enumerate() {
+			match add(&frequency, change) {
+				Some(next) => starts.push(mem::replace(&mut frequency, next)),
+				None => {
+					starts.push(frequency.clone());
+					overflow = Some(Overflow { change: i + 1 });
+					break;
+				}
+			}
+		}
+
+		let zero = Wide::default();
+		let mut visited = HashMap::new();
+
+		for (j, start) in starts.iter().enumerate() {
+			if let Some(k) = visited.insert(start, j) {
+				let repeat = start.clone();
+
+				return Ok(Some(self.repeat_at(
+					repeat,
+					(zero.clone(), j),
+					(zero, k),
+				)));
+			}
+		}
+
+		if let Some(overflow) = overflow {
+			return Err(overflow);
+		}
+
+		let drift = widen(&frequency);
+
+		// Every frequency comes back around after a single pass
+		if drift == zero {
+			return Ok(starts.first().map(|start| {
+				let one = Wide::from(1u8);
+
+				self.repeat_at(start.clone(), (one, 0), (zero, 0))
+			}));
+		}
+
+		// Frequencies from the first pass with the same remainder, in the
+		// order each later pass moves through them
+		let mut groups = HashMap::<Wide, Vec<(Wide, usize)>>::new();
+
+		for (i, start) in starts.iter().enumerate() {
+			let start = widen(start);
+
+			groups
+				.entry(residue(&start, &drift))
+				.or_default()
+				.push((start, i));
+		}
+
+		// The change made at `i` during pass `passes` first reaches the
+		// start of change `j`, so the repeat happens `passes * len + i`
+		// changes in
+		let len = Wide::from(starts.len() as u64);
+		let mut first: Option<(Wide, Wide, usize, usize)> = None;
+
+		for mut group in groups.into_values() {
+			group.sort_unstable();
+
+			if drift < zero {
+				group.reverse();
+			}
+
+			for pair in group.windows(2) {
+				let ((from, i), (repeat, j)) = (&pair[0], &pair[1]);
+				let passes = (repeat - from) / &drift;
+				let time = &passes * &len + Wide::from(*i as u64);
+
+				if first.as_ref().is_none_or(|(first, ..)| &time < first) {
+					first = Some((time, passes, *i, *j));
+				}
+			}
+		}
+
+		let (time, passes, i, j) = match first {
+			Some(first) => first,
+			None => return Ok(None),
+		};
+
+		if let Some(overflow) = first_overflow(&starts, &drift) {
+			// Changes too far in to count are reported as the last countable
+			if overflow < time {
+				return Err(Overflow {
+					change: usize::try_from(overflow).unwrap_or(usize::MAX),
+				});
+			}
+		}
+
+		Ok(Some(self.repeat_at(
+			starts[j].clone(),
+			(passes, i),
+			(zero, j),
+		)))
+	}
+
+	/// Every frequency reached twice, in the order each is first reached
+	/// again, with the first being `repeat`. Ends early if the frequency
+	/// goes out of range.
+	///
+	/// Unlike `repeat`, this cycles through the changes, remembering every
+	/// frequency along the way, so it's only practical for repeats that
+	/// come around within a modest number of passes. Unless the changes add
+	/// up to nothing, every repeated frequency is followed by another a
+	/// pass later, so there's no end to them.
+	pub fn repeats(&self) -> Repeats<'_> {
+		// Without any drift, everything that repeats has done so by the end
+		// of the second pass
+		let end = match self.repeat() {
+			Ok(Some(_)) if self.frequency() == Ok(Frequency::default()) => {
+				Some(2 * self.nums.len())
+			}
+			Ok(Some(_)) => None,
+			_ => Some(0),
+		};
+		let mut seen = HashMap::new();
+
+		seen.insert(Frequency::default(), (0, false));
+
+		Repeats {
+			day: self,
+			frequency: Frequency::default(),
+			changes: 0,
+			end,
+			seen,
+		}
+	}
+}
+
+/// Iterator over every repeated frequency, see `Day01::repeats`.
+#[derive(Debug)]
+pub struct Repeats<'a> {
+	day: &'a Day01,
+	frequency: Frequency,
+	// Changes made so far, stopping at `end` if there is one
+	changes: usize,
+	end: Option<usize>,
+	// How many changes in each frequency was first reached, and whether it's
+	// been reached again since
+	seen: HashMap<Frequency, (usize, bool)>,
+}
+
+impl Iterator for Repeats<'_> {
+	type Item = Repeat;
+
+	#[cfg_attr(not(feature = "bigint"), allow(clippy::clone_on_copy))]
+	fn next(&mut self) -> Option<Repeat> {
+		let len = self.day.nums.len();
+		// Pass and change within it, both counting from 0
+		let at = |changes: usize| {
+			(Wide::from((changes / len) as u64), changes % len)
+		};
+
+		while self.end.is_none_or(|end| self.changes < end) {
+			let change = &self.day.nums[self.changes % len];
+
+			self.frequency = match add(&self.frequency, change) {
+				Some(frequency) => frequency,
+				None => {
+					self.end = Some(self.changes);
+					return None;
+				}
+			};
+			self.changes += 1;
+
+			let changes = self.changes;
+			let (first, repeated) = self
+				.seen
+				.entry(self.frequency.clone())
+				.or_insert((changes, false));
+
+			if *first < changes && !*repeated {
+				*repeated = true;
+
+				return Some(self.day.repeat_at(
+					self.frequency.clone(),
+					at(changes),
+					at(*first),
+				));
+			}
+		}
+
+		None
+	}
+}
+
+impl Solution for Day01 {
+	const DAY: u32 = 1;
+
+	type Part1 = Frequency;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<Frequency> {
+		Ok(self.frequency()?)
+	}
+
+	fn part2(&self) -> common::Result<String> {
+		Ok(match self.first_repeat()? {
+			Some(repeat) => repeat.to_string(),
+			None => "none".to_owned(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
+	fn f(n: i64) -> Frequency {
+		Frequency::from(n)
+	}
+
+	fn day(nums: &[i64]) -> Day01 {
+		Day01::new(nums.iter().copied().map(f).collect())
+	}
+
+	#[test]
+	fn parse() {
+		let day: Day01 = "+1\n-2\n+3".parse().unwrap();
+
+		assert_eq!(day, self::day(&[1, -2, 3]));
+
+		assert_eq!(
+			"+1\n-2\n3x".parse::<Day01>(),
+			Err(ParseError::Invalid {
+				line: 3,
+				column: 1,
+				text: "3x".to_owned(),
+				expected: "a frequency change",
+			})
+		);
+	}
+
+	#[test]
+	fn frequency() {
+		assert_eq!(day(&[1, 1, 1]).frequency(), Ok(f(3)));
+		assert_eq!(day(&[1, 1, -2]).frequency(), Ok(f(0)));
+		assert_eq!(day(&[-1, -2, -3]).frequency(), Ok(f(-6)));
+	}
+
+	// Cycles through the changes until a frequency repeats, giving up after
+	// `limit` changes
+	fn simulate(nums: &[i64], limit: usize) -> Option<i64> {
+		let mut visited = HashSet::new();
+		let mut frequency = 0;
+
+		visited.insert(0);
+
+		nums.iter().cycle().take(limit).find_map(|change| {
+			frequency += change;
+
+			Some(frequency).filter(|&n| !visited.insert(n))
+		})
+	}
+
+	#[test]
+	fn first_repeat() {
+		assert_eq!(day(&[1, -1]).first_repeat(), Ok(Some(f(0))));
+		assert_eq!(day(&[3, 3, 4, -2, -4]).first_repeat(), Ok(Some(f(10))));
+		assert_eq!(day(&[-6, 3, 8, 5, -6]).first_repeat(), Ok(Some(f(5))));
+		assert_eq!(day(&[7, 7, -2, -7, -4]).first_repeat(), Ok(Some(f(14))));
+
+		// A long way round before anything repeats
+		assert_eq!(
+			day(&[1_000_000, -999_999]).first_repeat(),
+			Ok(Some(f(1_000_000)))
+		);
+	}
+
+	#[test]
+	fn repeat() {
+		let input = "+3\n\n+3\n+4\n-2\n-4";
+		let repeat = input.parse::<Day01>().unwrap().repeat().unwrap().unwrap();
+
+		assert_eq!(
+			repeat,
+			Repeat {
+				frequency: f(10),
+				at: Position {
+					line: 3,
+					pass: Wide::from(2u8),
+				},
+				first_seen: Some(Position {
+					line: 4,
+					pass: Wide::from(1u8),
+				}),
+			}
+		);
+		assert_eq!(
+			repeat.to_string(),
+			"10 on line 3 of pass 2, first seen on line 4 of pass 1"
+		);
+		assert_eq!(
+			repeat.to_json(),
+			r#"{"frequency":10,"at":{"line":3,"pass":2},"first_seen":{"line":4,"pass":1}}"#
+		);
+
+		// Back to the start at the end of the first pass
+		let repeat = day(&[1, 2, -3]).repeat().unwrap().unwrap();
+
+		assert_eq!(
+			repeat.to_string(),
+			"0 on line 3 of pass 1, first seen at the start"
+		);
+		assert_eq!(
+			repeat.to_json(),
+			r#"{"frequency":0,"at":{"line":3,"pass":1},"first_seen":null}"#
+		);
+
+		assert_eq!(day(&[1, 1]).repeat(), Ok(None));
+	}
+
+	#[test]
+	fn repeats() {
+		let frequencies = |day: Day01, n| {
+			day.repeats()
+				.take(n)
+				.map(|repeat| repeat.frequency)
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			frequencies(day(&[3, 3, 4, -2, -4]), 5),
+			[10, 8, 14, 12, 18]
+				.iter()
+				.copied()
+				.map(f)
+				.collect::<Vec<_>>()
+		);
+
+		// Everything has repeated by the end of the second pass
+		assert_eq!(
+			frequencies(day(&[1, 2, -3]), 10),
+			[0, 1, 3].iter().copied().map(f).collect::<Vec<_>>()
+		);
+
+		assert_eq!(frequencies(day(&[1, 1]), 10), vec![]);
+		assert_eq!(frequencies(day(&[]), 10), vec![]);
+	}
+
+	#[test]
+	fn calibrator() {
+		let mut calibrator = Calibrator::new();
+
+		assert_eq!(calibrator.push(f(3)), Ok(None));
+		assert_eq!(calibrator.push(f(3)), Ok(None));
+		assert_eq!(calibrator.push(f(4)), Ok(None));
+		assert_eq!(calibrator.push(f(-2)), Ok(None));
+		assert_eq!(calibrator.push(f(-4)), Ok(None));
+		assert_eq!(calibrator.push(f(3)), Ok(None));
+		assert_eq!(calibrator.first_repeat(), None);
+
+		calibrator.push_all(vec![f(3), f(4), f(-2), f(-4)]).unwrap();
+
+		assert_eq!(calibrator.first_repeat(), Some(f(10)));
+		assert_eq!(calibrator.frequency(), f(8));
+
+		// Only the first repeat is reported
+		assert_eq!(calibrator.push(f(-8)), Ok(None));
+		assert_eq!(calibrator.first_repeat(), Some(f(10)));
+
+		let mut calibrator = Calibrator::new();
+
+		calibrator.push_all(vec![f(1), f(-1)]).unwrap();
+
+		assert_eq!(calibrator.first_repeat(), Some(f(0)));
+	}
+
+	#[test]
+	fn read() {
+		let mut calibrator = Calibrator::new();
+
+		calibrator.read(&b"+7\n\n+7\n-2\n"[..]).unwrap();
+		calibrator.read(&b"-7\n-4\n"[..]).unwrap();
+
+		assert_eq!(calibrator.frequency(), f(1));
+		assert_eq!(calibrator.first_repeat(), None);
+
+		let err = calibrator.read(&b"+1\n\n+x\n"[..]).unwrap_err();
+
+		assert_eq!(
+			err.downcast_ref::<ParseError>(),
+			Some(&ParseError::Invalid {
+				line: 3,
+				column: 1,
+				text: "+x".to_owned(),
+				expected: "a frequency change",
+			})
+		);
+		assert_eq!(calibrator.frequency(), f(2));
+	}
+
+	#[test]
+	fn never_repeats() {
+		assert_eq!(day(&[1, 1]).first_repeat(), Ok(None));
+		assert_eq!(day(&[-3, 1]).first_repeat(), Ok(None));
+		assert_eq!(day(&[]).first_repeat(), Ok(None));
+		assert_eq!(day(&[1, 1]).part2().unwrap(), "none");
+	}
+
+	#[test]
+	fn first_repeat_matches_simulation() {
+		let mut rng = common::generate::Rng::new(1);
+
+		for _ in 0..2000 {
+			let len = 1 + rng.below(6);
+			let nums = (0..len).map(|_| rng.range(-6, 7)).collect::<Vec<_>>();
+
+			let day = day(&nums);
+
+			assert_eq!(
+				day.first_repeat(),
+				Ok(simulate(&nums, 1_000).map(f)),
+				"{:?}",
+				nums
+			);
+			assert_eq!(
+				day.repeats().next(),
+				day.repeat().unwrap(),
+				"{:?}",
+				nums
+			);
+		}
+	}
+
+	#[test]
+	#[cfg(not(feature = "bigint"))]
+	fn overflow() {
+		let max = i64::MAX;
+
+		assert_eq!(day(&[max, 1]).frequency(), Err(Overflow { change: 2 }));
+		assert_eq!(day(&[max, 1]).first_repeat(), Err(Overflow { change: 2 }));
+		assert_eq!(
+			day(&[max, 1]).part1().unwrap_err().to_string(),
+			"frequency out of range after change 2"
+		);
+
+		// Repeats before the overflow are still found
+		let parsed: Day01 = format!("+1\n-1\n{:+}\n+1", max).parse().unwrap();
+
+		assert_eq!(parsed.first_repeat(), Ok(Some(0)));
+		assert!(parsed.part1().is_err());
+		assert_eq!(parsed.part2().unwrap(), "0");
+
+		// Later passes go out of range before coming back around
+		assert_eq!(
+			day(&[i64::MIN, max]).first_repeat(),
+			Err(Overflow { change: 3 })
+		);
+		assert_eq!(day(&[i64::MIN, max]).repeats().next(), None);
+
+		// Or only after something repeats
+		let late = day(&[i64::MIN + 2, 1, max - 3]);
+
+		assert_eq!(
+			late.repeat().unwrap().unwrap().to_string(),
+			"-9223372036854775806 on line 2 of pass 2, first seen on line 1 \
+			 of pass 1"
+		);
+
+		// Cycling stops short of going out of range
+		let repeats = day(&[max, -max, 1]).repeats().collect::<Vec<_>>();
+
+		assert_eq!(repeats.len(), 1);
+		assert_eq!(repeats[0].frequency, 0);
+
+		let mut calibrator = Calibrator::new();
+
+		assert_eq!(calibrator.push(max), Ok(None));
+		assert_eq!(calibrator.push(1), Err(Overflow { change: 2 }));
+		assert_eq!(calibrator.frequency(), max);
+		assert_eq!(
+			calibrator.push_all(vec![-1, max, 1]),
+			Err(Overflow { change: 3 })
+		);
+		assert_eq!(calibrator.frequency(), max - 1);
+	}
+
+	#[test]
+	#[cfg(feature = "bigint")]
+	fn bigint() {
+		let day: Day01 = "+9223372036854775807\n+1".parse().unwrap();
+
+		assert_eq!(day.part1().unwrap().to_string(), "9223372036854775808");
+
+		let day: Day01 = "+9223372036854775807\n+9223372036854775807\n\
+			-18446744073709551613"
+			.parse()
+			.unwrap();
+
+		assert_eq!(day.part1().unwrap(), Frequency::from(1));
+		assert_eq!(day.part2().unwrap(), "9223372036854775807");
+	}
+
+	proptest! {
+		#[test]
+		#[cfg_attr(not(feature = "bigint"), allow(clippy::useless_conversion))]
+		fn round_trip(nums in vec(any::<i64>(), 1..50)) {
+			let day = Day01::new(nums.into_iter().map(Frequency::from).collect());
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
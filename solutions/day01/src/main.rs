@@ -1,83 +1,122 @@
-use std::collections::HashSet;
-use std::io::{self, BufRead};
+use std::io::BufReader;
 
-#[derive(Debug, PartialEq)]
-struct Day01 {
-	nums: Vec<i64>,
-}
+use common::cli::{self, Args, Format, Options, Profiler};
+use day01::{read_changes, Calibrator, Day01, Overflow, Repeat};
 
-impl<R> From<R> for Day01
-where
-	R: BufRead,
-{
-	fn from(reader: R) -> Self {
-		let nums = reader
-			.lines()
-			.map(|line| line.expect("Failed to read line"))
-			.map(|line| line.parse::<i64>().expect("Failed to parse number"))
-			.collect::<Vec<_>>();
-
-		Day01::new(nums)
-	}
-}
+#[cfg(feature = "heap")]
+#[global_allocator]
+static ALLOC: common::alloc::Counting = common::alloc::Counting;
 
-impl Day01 {
-	pub fn new(nums: Vec<i64>) -> Day01 {
-		Day01 { nums }
-	}
-
-	pub fn frequency(&self) -> i64 {
-		self.nums.iter().fold(0, |y, x| y + x)
+fn main() {
+	let args = Args::from_env(Options {
+		trace: true,
+		stream: true,
+		repeats: true,
+		..Options::NONE
+	});
+
+	if args.stream {
+		stream(&args);
+		return;
 	}
 
-	pub fn first_repeat(&self) -> i64 {
-		let nums = &self.nums;
-		let mut visited = HashSet::new();
+	cli::run::<Day01, _>(&args, &args.read_inputs(), |input, profiler| {
+		let day: Day01 = profiler.step("parse", || input.parse())?;
 
-		visited.insert(0);
+		if let Some(format) = args.trace {
+			trace(&day, format, args.repeats, profiler)?;
+			return Ok(());
+		}
 
-		(0..)
-			.map(|i| nums[i % nums.len()])
-			.scan(0, |y, x| {
-				*y += x;
+		let frequency = profiler.step("part 1", || day.frequency());
 
-				Some(*y)
-			})
-			.find(|&n| visited.replace(n).is_some())
-			.expect("Did not find a repeat")
-	}
-}
+		if let Ok(frequency) = &frequency {
+			println!("Frequency: {}", frequency);
+		}
 
-fn main() {
-	let day: Day01 = io::stdin().lock().into();
+		let first_repeat = profiler.step("part 2", || day.first_repeat())?;
 
-	println!("Frequency: {}", day.frequency());
-	println!("First Repeat: {}", day.first_repeat());
-}
+		match first_repeat {
+			Some(repeat) => println!("First Repeat: {}", repeat),
+			None => println!("First Repeat: none"),
+		}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
+		// The first repeat can come before the frequency goes out of range
+		frequency?;
 
-	#[test]
-	fn parse() {
-		let day: Day01 = "+1\n-2\n+3".as_bytes().into();
+		Ok(())
+	});
+}
 
-		assert_eq!(day, Day01::new(vec![1, -2, 3]));
+// Prints where the first repeat was reached and, given `--repeats <n>`, the
+// first `n` repeated frequencies
+fn trace(
+	day: &Day01,
+	format: Format,
+	n: Option<usize>,
+	profiler: &mut Profiler,
+) -> Result<(), Overflow> {
+	let first = profiler.step("repeat", || day.repeat())?;
+	let repeats = n.map(|n| {
+		profiler.step("repeats", || day.repeats().take(n).collect::<Vec<_>>())
+	});
+
+	match format {
+		Format::Text => {
+			match &first {
+				Some(repeat) => println!("First Repeat: {}", repeat),
+				None => println!("First Repeat: none"),
+			}
+
+			for (i, repeat) in repeats.iter().flatten().enumerate() {
+				println!("{}: {}", i + 1, repeat);
+			}
+		}
+		Format::Json => {
+			let first = first
+				.as_ref()
+				.map_or_else(|| "null".to_owned(), Repeat::to_json);
+
+			match repeats {
+				Some(repeats) => {
+					let repeats =
+						repeats.iter().map(Repeat::to_json).collect::<Vec<_>>();
+
+					println!(
+						r#"{{"first_repeat":{},"repeats":[{}]}}"#,
+						first,
+						repeats.join(",")
+					);
+				}
+				None => println!(r#"{{"first_repeat":{}}}"#, first),
+			}
+		}
 	}
 
-	#[test]
-	fn frequency() {
-		assert_eq!(Day01::new(vec![1, 1, 1]).frequency(), 3);
-		assert_eq!(Day01::new(vec![1, 1, -2]).frequency(), 0);
-		assert_eq!(Day01::new(vec![-1, -2, -3]).frequency(), -6);
-	}
+	Ok(())
+}
 
-	#[test]
-	fn first_repeat() {
-		assert_eq!(Day01::new(vec![1, -1]).first_repeat(), 0);
-		assert_eq!(Day01::new(vec![3, 3, 4, -2, -4]).first_repeat(), 10);
-		assert_eq!(Day01::new(vec![-6, 3, 8, 5, -6]).first_repeat(), 5);
-		assert_eq!(Day01::new(vec![7, 7, -2, -7, -4]).first_repeat(), 14);
-	}
+// Applies each change as it's read, printing the first repeat as soon as
+// it's reached. Only a single pass is made through the changes. Parsing the
+// arguments rejects `--format json`, as there's no report to print.
+fn stream(args: &Args) {
+	cli::stream(args, |reader, profiler| {
+		profiler.step("calibrate", || {
+			let mut calibrator = Calibrator::new();
+
+			for change in read_changes(BufReader::new(reader)) {
+				if let Some(repeat) = calibrator.push(change?)? {
+					println!("First Repeat: {}", repeat);
+				}
+			}
+
+			println!("Frequency: {}", calibrator.frequency());
+
+			if calibrator.first_repeat().is_none() {
+				println!("First Repeat: none in a single pass");
+			}
+
+			Ok(())
+		})
+	});
 }
@@ -0,0 +1,94 @@
+//! Random box IDs with known answers
+
+use common::generate::{Generated, Rng};
+
+fn differences(left: &str, right: &str) -> usize {
+	left.bytes()
+		.zip(right.bytes())
+		.filter(|(l, r)| l != r)
+		.count()
+}
+
+// Random ID that differs from every other ID in at least two places
+fn unique_id(rng: &mut Rng, ids: &[String]) -> String {
+	loop {
+		let id = (0..26).map(|_| rng.letter()).collect::<String>();
+
+		if ids.iter().all(|other| differences(&id, other) > 1) {
+			return id;
+		}
+	}
+}
+
+fn checksum(ids: &[String]) -> usize {
+	let (twos, threes) = ids.iter().fold((0, 0), |(twos, threes), id| {
+		let mut counts = [0; 26];
+
+		id.bytes().for_each(|b| counts[(b - b'a') as usize] += 1);
+
+		(
+			twos + counts.contains(&2) as usize,
+			threes + counts.contains(&3) as usize,
+		)
+	});
+
+	twos * threes
+}
+
+/// Generates `n` (at least 2) box IDs of 26 letters. Exactly one pair of IDs
+/// differs by a single character.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<usize, String> {
+	let mut ids = Vec::with_capacity(n.max(2));
+
+	while ids.len() < n.max(2) - 1 {
+		let id = unique_id(rng, &ids);
+
+		ids.push(id);
+	}
+
+	let (similar, common) = loop {
+		let original = &ids[rng.below(ids.len())];
+		let position = rng.below(26);
+		let mut similar = original.clone().into_bytes();
+
+		similar[position] = b'a' + (similar[position] - b'a' + 1) % 26;
+
+		let similar = String::from_utf8(similar).unwrap();
+		let near = ids.iter().filter(|id| differences(&similar, id) <= 1);
+
+		if near.count() == 1 {
+			let common = [&original[..position], &original[(position + 1)..]];
+
+			break (similar, common.concat());
+		}
+	};
+
+	ids.push(similar);
+	rng.shuffle(&mut ids);
+
+	Generated {
+		part1: Some(checksum(&ids)),
+		part2: Some(common),
+		input: ids.join("\n"),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day02;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(2);
+
+		for n in &[2, 10, 250] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day02 = generated.input.parse().unwrap();
+			let matches = day.matches().collect::<Vec<_>>().join("\n");
+
+			assert_eq!(Some(day.checksum()), generated.part1);
+			assert_eq!(Some(matches), generated.part2);
+		}
+	}
+}
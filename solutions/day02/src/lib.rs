@@ -0,0 +1,333 @@
+//! Day 2: Inventory Management System
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, ParseError};
+use common::Solution;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// A list of box IDs, one per input line.
+#[derive(Debug, PartialEq)]
+pub struct Day02 {
+	lines: Vec<String>,
+}
+
+// The two earliest IDs in a bucket with different letters at its masked
+// position, as their index and letter
+#[derive(Default)]
+struct Earliest {
+	first: Option<(usize, char)>,
+	other: Option<(usize, char)>,
+}
+
+impl Earliest {
+	// Earliest ID without `letter` at the masked position
+	fn unlike(&self, letter: char) -> Option<usize> {
+		match self.first {
+			Some((i, first)) if first != letter => Some(i),
+			_ => self.other.map(|(i, _)| i),
+		}
+	}
+
+	// Adds an ID that comes before every ID added so far
+	fn push(&mut self, i: usize, letter: char) {
+		if let Some(first) = self.first.filter(|&(_, first)| first != letter) {
+			self.other = Some(first);
+		}
+
+		self.first = Some((i, letter));
+	}
+}
+
+impl FromStr for Day02 {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let lines = parse::parse_lines(s, |line| Ok(line.trim().to_owned()))?;
+
+		Ok(Day02::new(lines))
+	}
+}
+
+/// Writes one box ID per line.
+impl fmt::Display for Day02 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.lines.join("\n"))
+	}
+}
+
+impl Day02 {
+	/// Creates an inventory from a list of box IDs.
+	pub fn new<I, S>(lines: I) -> Day02
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		let lines = lines.into_iter().map(|s| s.into()).collect();
+
+		Day02 { lines }
+	}
+
+	/// Number of positions at which two IDs differ.
+	pub fn delta(left: &str, right: &str) -> usize {
+		left.chars()
+			.zip(right.chars())
+			.fold(0, |acc, (a, b)| acc + (a != b) as usize)
+	}
+
+	/// Letters common to both IDs at the same position.
+	pub fn diff(left: &str, right: &str) -> String {
+		left.chars()
+			.zip(right.chars())
+			.filter(|(a, b)| a == b)
+			.map(|(a, _)| a)
+			.collect()
+	}
+
+	/// Count of IDs with a letter appearing exactly twice multiplied by the
+	/// count with a letter appearing exactly three times (part 1).
+	pub fn checksum(&self) -> usize {
+		let (twice, thrice) = self
+			.lines
+			.iter()
+			// Count instances of each character
+			.map(|line| {
+				line.chars().fold(HashMap::new(), |mut acc, c| {
+					*acc.entry(c).or_insert(0) += 1;
+
+					acc
+				})
+			})
+			// Did this map include double or triple occurances?
+			.map(|acc| {
+				let has_two = acc.values().find(|&&n| n == 2).is_some();
+				let has_three = acc.values().find(|&&n| n == 3).is_some();
+
+				(has_two, has_three)
+			})
+			// Sum the totals
+			.fold((0, 0), |(twos, threes), (has_two, has_three)| {
+				(twos + has_two as usize, threes + has_three as usize)
+			});
+
+		twice * thrice
+	}
+
+	/// Common letters of each ID and the first later ID that differs from it
+	/// by exactly one character (part 2). IDs of different lengths are
+	/// compared over the length of the shorter, like `delta` does.
+	///
+	/// Rather than comparing every pair of IDs, the IDs are bucketed by the
+	/// letters either side of each position in turn, so millions of IDs are
+	/// fine. IDs of different lengths are also bucketed by their prefix at
+	/// each shorter length, which costs nothing when every ID is the same
+	/// length.
+	pub fn matches(&self) -> impl Iterator<Item = String> + '_ {
+		self.partners().into_iter().enumerate().filter_map(
+			move |(i, partner)| {
+				Some(Day02::diff(&self.lines[i], &self.lines[partner?]))
+			},
+		)
+	}
+
+	// Index of the first later ID differing from each ID by one character
+	fn partners(&self) -> Vec<Option<usize>> {
+		let lengths = self
+			.lines
+			.iter()
+			.map(|line| line.chars().count())
+			.collect::<BTreeSet<_>>();
+		let mut partners = vec![None; self.lines.len()];
+
+		for len in lengths {
+			self.partners_of_length(len, &mut partners);
+		}
+
+		partners
+	}
+
+	// Finds partners among the IDs with at least `len` letters, comparing
+	// only their first `len` letters. Longer IDs are only compared with IDs
+	// of exactly `len` letters here, and with each other at their own length.
+	fn partners_of_length(&self, len: usize, partners: &mut [Option<usize>]) {
+		let ids = self
+			.lines
+			.iter()
+			.enumerate()
+			.filter_map(|(i, line)| {
+				let mut letters =
+					line.chars().take(len + 1).collect::<Vec<_>>();
+				let exact = letters.len() == len;
+
+				letters.truncate(len);
+
+				Some((i, exact, letters)).filter(|(_, _, l)| l.len() == len)
+			})
+			.collect::<Vec<_>>();
+
+		// Numbers for the last `k` letters of each ID, for every `k` below
+		// `len`, equal exactly when the letters are
+		let mut suffixes = vec![vec![0; ids.len()]];
+
+		for k in 1..len {
+			let letters = ids.iter().map(|(_, _, letters)| letters[len - k]);
+
+			suffixes.push(number(suffixes[k - 1].iter().copied().zip(letters)));
+		}
+
+		// Numbers for the first `p` letters of each ID
+		let mut prefixes = vec![0; ids.len()];
+
+		for p in 0..len {
+			let suffixes = &suffixes[len - 1 - p];
+
+			// IDs by the letters either side of `p`, both of any length and
+			// of exactly `len` letters. Working backwards, the buckets only
+			// ever hold later IDs.
+			let mut buckets =
+				HashMap::<(u32, u32), (Earliest, Earliest)>::new();
+
+			for (n, (i, exact, letters)) in ids.iter().enumerate().rev() {
+				let (any, exactly) =
+					buckets.entry((prefixes[n], suffixes[n])).or_default();
+				let letter = letters[p];
+
+				// A longer ID is only compared with IDs of this length
+				let partner = if *exact {
+					any.unlike(letter)
+				} else {
+					exactly.unlike(letter)
+				};
+
+				partners[*i] = partners[*i].into_iter().chain(partner).min();
+				any.push(*i, letter);
+
+				if *exact {
+					exactly.push(*i, letter);
+				}
+			}
+
+			let letters = ids.iter().map(|(_, _, letters)| letters[p]);
+
+			prefixes = number(prefixes.into_iter().zip(letters));
+		}
+	}
+}
+
+// Numbers each distinct pair in the order they first appear
+fn number(pairs: impl Iterator<Item = (u32, char)>) -> Vec<u32> {
+	let mut numbers = HashMap::new();
+
+	pairs
+		.map(|pair| {
+			let next = numbers.len() as u32;
+
+			*numbers.entry(pair).or_insert(next)
+		})
+		.collect()
+}
+
+impl Solution for Day02 {
+	const DAY: u32 = 2;
+
+	type Part1 = usize;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<usize> {
+		Ok(self.checksum())
+	}
+
+	fn part2(&self) -> common::Result<String> {
+		Ok(self.matches().collect::<Vec<_>>().join("\n"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
+	#[test]
+	fn parse() {
+		let day: Day02 = "a\nbbb\ncc".parse().unwrap();
+		let lines = vec!["a", "bbb", "cc"];
+
+		assert_eq!(day, Day02::new(lines));
+	}
+
+	#[test]
+	fn checksum() {
+		let day = Day02::new(vec![
+			"abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee",
+			"ababab",
+		]);
+
+		assert_eq!(day.checksum(), 12);
+	}
+
+	#[test]
+	fn matches() {
+		let day = Day02::new(vec![
+			"abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+		]);
+
+		let matches = day.matches().collect::<Vec<_>>();
+
+		assert_eq!(matches, vec!["fgij"]);
+	}
+
+	// Compares each ID with every later ID
+	fn pairwise(lines: &[String]) -> Vec<String> {
+		lines
+			.iter()
+			.enumerate()
+			.filter_map(|(i, left)| {
+				lines[i..]
+					.iter()
+					.find(|right| Day02::delta(left, right) == 1)
+					.map(|right| Day02::diff(left, right))
+			})
+			.collect()
+	}
+
+	#[test]
+	fn matches_pairwise() {
+		let mut rng = common::generate::Rng::new(2);
+
+		for _ in 0..2000 {
+			let lines = (0..(1 + rng.below(8)))
+				.map(|_| {
+					(0..(1 + rng.below(4)))
+						.map(|_| ['a', 'b', 'é'][rng.below(3)])
+						.collect::<String>()
+				})
+				.collect::<Vec<_>>();
+			let day = Day02::new(lines.clone());
+
+			assert_eq!(
+				day.matches().collect::<Vec<_>>(),
+				pairwise(&lines),
+				"{:?}",
+				lines
+			);
+		}
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(lines in vec("\\S{1,12}", 1..20)) {
+			let day = Day02::new(lines);
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
@@ -0,0 +1,111 @@
+//! Random claims with known answers
+
+use std::collections::HashMap;
+
+use common::generate::{Generated, Rng};
+
+use crate::Claim;
+
+// Every claim but the lonely one fits within this many inches
+const FABRIC: i64 = 1000;
+
+fn claim(rng: &mut Rng, id: usize, l: i64, t: i64) -> Claim {
+	Claim {
+		id,
+		l: l as usize,
+		t: t as usize,
+		w: rng.range(5, 30) as usize,
+		h: rng.range(5, 30) as usize,
+	}
+}
+
+// Random claim overlapping `other`
+fn overlapping(rng: &mut Rng, other: &Claim) -> Claim {
+	let mut claim = claim(rng, 0, 0, 0);
+	let (l, w) = (other.l as i64, other.w as i64);
+	let (t, h) = (other.t as i64, other.h as i64);
+
+	claim.l = rng.range(l - claim.w as i64 + 1, l + w).max(0) as usize;
+	claim.t = rng.range(t - claim.h as i64 + 1, t + h).max(0) as usize;
+	claim.l = claim.l.min((FABRIC as usize) - claim.w);
+	claim.t = claim.t.min((FABRIC as usize) - claim.h);
+	claim
+}
+
+fn total_overlapped(claims: &[Claim]) -> usize {
+	let mut fabric = HashMap::new();
+
+	for claim in claims {
+		for x in claim.l..(claim.l + claim.w) {
+			for y in claim.t..(claim.t + claim.h) {
+				*fabric.entry((x, y)).or_insert(0) += 1;
+			}
+		}
+	}
+
+	fabric.values().filter(|&&n| n > 1).count()
+}
+
+/// Generates `n` (at least 3) claims. Every claim overlaps another except
+/// for a single claim placed to the right of the others.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<usize, String> {
+	let n = n.max(3);
+	let mut claims = Vec::with_capacity(n);
+
+	while claims.len() < n - 1 {
+		let claim = if claims.len() % 2 == 0 && claims.len() + 2 < n {
+			let (l, t) = (rng.range(0, FABRIC - 30), rng.range(0, FABRIC - 30));
+
+			claim(rng, 0, l, t)
+		} else {
+			overlapping(rng, &claims[claims.len() - 1])
+		};
+
+		claims.push(claim);
+	}
+
+	let lonely = rng.below(n);
+	let t = rng.range(0, FABRIC - 30);
+
+	claims.insert(lonely, claim(rng, 0, FABRIC + 10, t));
+	claims
+		.iter_mut()
+		.enumerate()
+		.for_each(|(i, claim)| claim.id = i + 1);
+
+	let input = claims
+		.iter()
+		.map(|Claim { id, l, t, w, h }| {
+			format!("#{} @ {},{}: {}x{}\n", id, l, t, w, h)
+		})
+		.collect();
+
+	Generated {
+		input,
+		part1: Some(total_overlapped(&claims)),
+		part2: Some((lonely + 1).to_string()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day03;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(3);
+
+		for n in &[3, 4, 10, 500] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day03 = generated.input.parse().unwrap();
+			let non_overlapped = day
+				.non_overlapped()
+				.map(|claim| claim.id.to_string())
+				.collect::<Vec<_>>();
+
+			assert_eq!(Some(day.total_overlapped()), generated.part1);
+			assert_eq!(Some(non_overlapped.join("\n")), generated.part2);
+		}
+	}
+}
@@ -0,0 +1,300 @@
+//! Day 3: No Matter How You Slice It
+
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+use grid::Grid;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Most square inches of fabric worth laying claims out on
+const MAX_AREA: usize = 1 << 24;
+
+/// A rectangular claim on the fabric, e.g. `#1 @ 861,330: 20x10`.
+#[derive(Debug, PartialEq)]
+pub struct Claim {
+	/// Claim identifier
+	pub id: usize,
+	/// Inches from the left edge of the fabric
+	pub l: usize,
+	/// Inches from the top edge of the fabric
+	pub t: usize,
+	/// Width in inches
+	pub w: usize,
+	/// Height in inches
+	pub h: usize,
+}
+
+impl FromStr for Claim {
+	type Err = ParseError;
+
+	// #1 @ 861,330: 20x10
+	fn from_str(line: &str) -> Result<Self, ParseError> {
+		let mut cursor = Cursor::new(line);
+
+		cursor.skip_whitespace().literal("#", "`#`")?;
+		let id = cursor.integer("a claim id")?;
+		cursor.skip_whitespace().literal("@", "`@`")?;
+		let l = cursor.skip_whitespace().integer::<usize>("a left edge")?;
+		cursor.literal(",", "`,`")?;
+		let t = cursor.integer::<usize>("a top edge")?;
+		cursor.literal(":", "`:`")?;
+		let width = cursor.skip_whitespace().rest();
+		let w = cursor.integer::<usize>("a width")?;
+		let width = &width[..(width.len() - cursor.rest().len())];
+		cursor.literal("x", "`x`")?;
+		let height = cursor.rest();
+		let h = cursor.integer::<usize>("a height")?;
+		let height = &height[..(height.len() - cursor.rest().len())];
+		cursor.end()?;
+
+		// The far edges must be addressable to lay the claim out
+		if l.checked_add(w).is_none() {
+			return Err(ParseError::invalid(line, width, "a smaller width"));
+		}
+
+		if t.checked_add(h).is_none() {
+			return Err(ParseError::invalid(line, height, "a smaller height"));
+		}
+
+		Ok(Claim { id, l, t, w, h })
+	}
+}
+
+impl fmt::Display for Claim {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let Claim { id, l, t, w, h } = self;
+
+		write!(f, "#{} @ {},{}: {}x{}", id, l, t, w, h)
+	}
+}
+
+// Left, top, right and bottom edges of the fabric under a set of claims
+type Extent = (usize, usize, usize, usize);
+
+// Grows `extent` to cover `claim` as well
+fn extend(extent: Option<Extent>, claim: &Claim) -> Extent {
+	let &Claim { l, t, w, h, .. } = claim;
+	let (r, b) = (l + w, t + h);
+
+	match extent {
+		Some((l0, t0, r0, b0)) => (l0.min(l), t0.min(t), r0.max(r), b0.max(b)),
+		None => (l, t, r, b),
+	}
+}
+
+/// A set of claims and the number of claims covering each square inch.
+#[derive(Debug, PartialEq)]
+pub struct Day03 {
+	claims: Vec<Claim>,
+	// Left and top edges of the fabric
+	origin: (usize, usize),
+	fabric: Grid<usize>,
+}
+
+impl Day03 {
+	/// Lays out every claim on a piece of fabric just large enough to hold
+	/// them all, starting from the nearest claim rather than the corner.
+	/// Parsing rejects claims spread over too much fabric to lay out.
+	pub fn new(claims: Vec<Claim>) -> Self {
+		let (left, top, right, bottom) = claims
+			.iter()
+			.fold(None, |extent, claim| Some(extend(extent, claim)))
+			.unwrap_or_default();
+
+		let mut fabric = Grid::new(right - left, bottom - top, 0);
+
+		for &Claim { l, t, w, h, .. } in &claims {
+			let (l, t) = (l - left, t - top);
+
+			for y in t..(t + h) {
+				for x in l..(l + w) {
+					fabric[(x, y)] += 1;
+				}
+			}
+		}
+
+		Day03 {
+			claims,
+			origin: (left, top),
+			fabric,
+		}
+	}
+
+	/// Claims in input order.
+	pub fn claims(&self) -> &[Claim] {
+		&self.claims
+	}
+
+	/// Square inches of fabric within two or more claims (part 1).
+	pub fn total_overlapped(&self) -> usize {
+		self.fabric.iter().filter(|(_, &n)| n > 1).count()
+	}
+
+	/// Claims that do not overlap any other claim (part 2).
+	pub fn non_overlapped(&self) -> impl Iterator<Item = &Claim> {
+		let (left, top) = self.origin;
+
+		self.claims
+			.iter()
+			.filter(move |&&Claim { l, t, w, h, .. }| {
+				self.fabric
+					.view(l - left, t - top, w, h)
+					.is_some_and(|view| view.cells().all(|&n| n <= 1))
+			})
+	}
+}
+
+impl FromStr for Day03 {
+	type Err = ParseError;
+
+	/// Parses one claim per line, failing on the first claim that spreads
+	/// the fabric over more than `MAX_AREA` square inches.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let claims = parse::parse_lines(s, str::parse)?;
+		let mut extent = None;
+
+		for ((number, line), claim) in parse::lines(s).zip(&claims) {
+			let (l, t, r, b) = extend(extent, claim);
+
+			if (r - l)
+				.checked_mul(b - t)
+				.is_none_or(|area| area > MAX_AREA)
+			{
+				let expected = "a claim closer to the others";
+
+				return Err(ParseError::invalid(line, line.trim(), expected)
+					.on_line(number));
+			}
+
+			extent = Some((l, t, r, b));
+		}
+
+		Ok(Day03::new(claims))
+	}
+}
+
+impl Solution for Day03 {
+	const DAY: u32 = 3;
+
+	type Part1 = usize;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<usize> {
+		Ok(self.total_overlapped())
+	}
+
+	fn part2(&self) -> common::Result<String> {
+		Ok(self
+			.non_overlapped()
+			.map(|Claim { id, .. }| id.to_string())
+			.collect::<Vec<_>>()
+			.join("\n"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	#[test]
+	fn total_overlapped() {
+		let day: Day03 = r#"
+			#1 @ 1,3: 4x4
+			#2 @ 3,1: 4x4
+			#3 @ 5,5: 2x2
+		"#
+		.parse()
+		.unwrap();
+
+		assert_eq!(day.total_overlapped(), 4);
+	}
+
+	#[test]
+	fn non_overlapped() {
+		let day: Day03 = r#"
+			#1 @ 1,3: 4x4
+			#2 @ 3,1: 4x4
+			#3 @ 5,5: 2x2
+		"#
+		.parse()
+		.unwrap();
+
+		let non_overlapped = day
+			.non_overlapped()
+			.map(|Claim { id, .. }| *id)
+			.collect::<Vec<_>>();
+
+		assert_eq!(non_overlapped, vec![3_usize]);
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"#1 @ 1,3: 4x4\n#2 @ 3 1: 4x4".parse::<Day03>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 8,
+				text: "1:".to_owned(),
+				expected: "`,`",
+			})
+		);
+
+		assert_eq!(
+			"#1 @ 1,18446744073709551615: 4x1".parse::<Day03>(),
+			Err(ParseError::Invalid {
+				line: 1,
+				column: 32,
+				text: "1".to_owned(),
+				expected: "a smaller height",
+			})
+		);
+
+		assert_eq!(
+			"#1 @ 1,1: 4x4\n\n#2 @ 3000000000,3000000000: 1x1".parse::<Day03>(),
+			Err(ParseError::Invalid {
+				line: 3,
+				column: 1,
+				text: "#2 @ 3000000000,3000000000: 1x1".to_owned(),
+				expected: "a claim closer to the others",
+			})
+		);
+	}
+
+	#[test]
+	fn far_from_the_corner() {
+		let day: Day03 = r#"
+			#1 @ 3000000001,3000000003: 4x4
+			#2 @ 3000000003,3000000001: 4x4
+			#3 @ 3000000005,3000000005: 2x2
+		"#
+		.parse()
+		.unwrap();
+
+		assert_eq!(day.total_overlapped(), 4);
+		assert_eq!(day.part2().unwrap(), "3");
+	}
+
+	// An edge along with a length that keeps the far edge addressable
+	fn span() -> impl Strategy<Value = (usize, usize)> {
+		any::<usize>()
+			.prop_flat_map(|edge| (Just(edge), 0..=(usize::MAX - edge)))
+	}
+
+	proptest! {
+		#[test]
+		fn claim_round_trip(id in any::<usize>(), (l, w) in span(), (t, h) in span()) {
+			let claim = Claim { id, l, t, w, h };
+
+			prop_assert_eq!(claim.to_string().parse(), Ok(claim));
+		}
+	}
+}
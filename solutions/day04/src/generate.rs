@@ -0,0 +1,164 @@
+//! Random guard logs with known answers
+
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate};
+use common::generate::{Generated, Rng};
+
+use crate::Id;
+
+// A guard's shift and the minutes they fell asleep and woke up
+struct Shift {
+	id: Id,
+	sleeps: Vec<(usize, usize)>,
+}
+
+// Sleeps within the first hour of a shift, never overlapping
+fn sleeps(rng: &mut Rng) -> Vec<(usize, usize)> {
+	let mut minutes = (0..(2 * rng.below(4)))
+		.map(|_| rng.range(1, 60) as usize)
+		.collect::<Vec<_>>();
+
+	minutes.sort_unstable();
+	minutes.dedup();
+
+	minutes
+		.chunks_exact(2)
+		.map(|pair| (pair[0], pair[1]))
+		.collect()
+}
+
+// The sleepy guard dozes off before `minute` and wakes up after it
+fn long_sleep(rng: &mut Rng, minute: usize) -> (usize, usize) {
+	let start = rng.range(1, minute as i64 + 1) as usize;
+	let end = rng.range(minute as i64 + 1, 60) as usize;
+
+	(start, end)
+}
+
+// Unique maximum of `f`
+fn unique_max<T: Copy, K: Ord>(
+	items: impl Iterator<Item = T>,
+	f: impl Fn(&T) -> K,
+) -> Option<T> {
+	let mut items = items.collect::<Vec<_>>();
+
+	items.sort_by_key(|item| f(item));
+
+	match items.as_slice() {
+		[.., a, b] if f(a) == f(b) => None,
+		[.., b] => Some(*b),
+		[] => None,
+	}
+}
+
+// Answers for both parts, unless there is a tie for either
+fn answers(shifts: &[Shift]) -> Option<(usize, usize)> {
+	let mut minutes = HashMap::<Id, [u32; 60]>::new();
+
+	for Shift { id, sleeps } in shifts {
+		let counts = minutes.entry(*id).or_insert([0; 60]);
+
+		for &(start, end) in sleeps {
+			counts[start..end].iter_mut().for_each(|n| *n += 1);
+		}
+	}
+
+	let most_asleep =
+		unique_max(minutes.iter(), |(_, counts)| counts.iter().sum::<u32>())?;
+
+	let part1 = unique_max(most_asleep.1.iter().enumerate(), |(_, n)| **n)
+		.map(|(minute, _)| most_asleep.0 * minute)?;
+
+	let part2 = unique_max(
+		minutes.iter().flat_map(|(id, counts)| {
+			counts.iter().enumerate().map(move |m| (id, m))
+		}),
+		|(_, (_, n))| **n,
+	)
+	.map(|(id, (minute, _))| id * minute)?;
+
+	Some((part1, part2))
+}
+
+/// Generates logs for `n` shifts worked by roughly one guard per 15 shifts.
+/// One guard sleeps through the same minute every shift so that the answers
+/// are unique.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<usize, usize> {
+	let guards = (0..(n / 15).max(2))
+		.map(|_| rng.range(10, 4000) as Id)
+		.collect::<Vec<_>>();
+
+	let (shifts, (part1, part2)) = loop {
+		let minute = rng.range(5, 55) as usize;
+		let shifts = (0..n)
+			.map(|_| {
+				let id = guards[rng.below(guards.len())];
+				let sleeps = if id == guards[0] {
+					vec![long_sleep(rng, minute)]
+				} else {
+					sleeps(rng)
+				};
+
+				Shift { id, sleeps }
+			})
+			.collect::<Vec<_>>();
+
+		if let Some(answers) = answers(&shifts) {
+			break (shifts, answers);
+		}
+	};
+
+	let first = NaiveDate::from_ymd_opt(1518, 2, 1).unwrap();
+	let mut logs = Vec::new();
+
+	for (i, Shift { id, sleeps }) in shifts.iter().enumerate() {
+		let date = first + Duration::days(i as i64);
+
+		// Guards often arrive shortly before midnight
+		let begin = if rng.one_in(2) {
+			let before = date - Duration::days(1);
+
+			format!("{} 23:{:02}", before.format("%Y-%m-%d"), rng.range(45, 60))
+		} else {
+			format!("{} 00:00", date.format("%Y-%m-%d"))
+		};
+
+		logs.push(format!("[{}] Guard #{} begins shift", begin, id));
+
+		for (start, end) in sleeps {
+			let date = date.format("%Y-%m-%d");
+
+			logs.push(format!("[{} 00:{:02}] falls asleep", date, start));
+			logs.push(format!("[{} 00:{:02}] wakes up", date, end));
+		}
+	}
+
+	rng.shuffle(&mut logs);
+
+	Generated {
+		input: logs.join("\n"),
+		part1: Some(part1),
+		part2: Some(part2),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day04;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(4);
+
+		for n in &[1, 10, 500] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day04 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+			assert_eq!(day.part2().ok(), generated.part2);
+		}
+	}
+}
@@ -0,0 +1,409 @@
+//! Day 4: Repose Record
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::offset::TimeZone;
+use chrono::{DateTime, NaiveDateTime, Timelike, Utc};
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// Guard identifier
+pub type Id = usize;
+
+/// Something observed in a log entry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+	/// `Guard #10 begins shift`
+	Shift(Id),
+	/// `falls asleep`
+	Asleep,
+	/// `wakes up`
+	Wake,
+}
+
+impl fmt::Display for Event {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Event::Shift(id) => write!(f, "Guard #{} begins shift", id),
+			Event::Asleep => write!(f, "falls asleep"),
+			Event::Wake => write!(f, "wakes up"),
+		}
+	}
+}
+
+/// A single timestamped entry, e.g. `[1518-11-01 00:00] Guard #10 begins
+/// shift`.
+#[derive(Debug, PartialEq)]
+pub struct Log {
+	pub timestamp: DateTime<Utc>,
+	pub event: Event,
+}
+
+impl FromStr for Log {
+	type Err = ParseError;
+
+	// [1518-11-01 00:00] Guard #10 begins shift
+	fn from_str(line: &str) -> Result<Self, ParseError> {
+		let mut cursor = Cursor::new(line);
+
+		cursor.skip_whitespace().literal("[", "`[`")?;
+
+		let timestamp = cursor.take_while(|c| c != ']', "a timestamp")?;
+		let timestamp =
+			NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M")
+				.map_err(|_| {
+					ParseError::invalid(
+						line,
+						timestamp,
+						"a `YYYY-MM-DD HH:MM` time",
+					)
+				})?;
+
+		cursor.literal("]", "`]`")?.skip_whitespace();
+
+		let event = if cursor.literal("Guard", "").is_ok() {
+			cursor.skip_whitespace().literal("#", "`#`")?;
+
+			let id = cursor.integer("a guard id")?;
+
+			cursor
+				.skip_whitespace()
+				.literal("begins shift", "`begins shift`")?;
+
+			Event::Shift(id)
+		} else if cursor.literal("falls asleep", "").is_ok() {
+			Event::Asleep
+		} else if cursor.literal("wakes up", "").is_ok() {
+			Event::Wake
+		} else {
+			return Err(cursor.unexpected("a guard event"));
+		};
+
+		cursor.end()?;
+
+		Ok(Log {
+			event,
+			timestamp: Utc.from_utc_datetime(&timestamp),
+		})
+	}
+}
+
+impl fmt::Display for Log {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"[{}] {}",
+			self.timestamp.format("%Y-%m-%d %H:%M"),
+			self.event
+		)
+	}
+}
+
+/// Guard logs in chronological order.
+#[derive(Debug, PartialEq)]
+pub struct Day04 {
+	logs: Vec<Log>,
+}
+
+impl Day04 {
+	/// Creates a record from log entries in any order.
+	pub fn new(mut logs: Vec<Log>) -> Self {
+		logs.sort_by_key(|l| l.timestamp);
+
+		Day04 { logs }
+	}
+
+	/// Identifiers of every guard that started a shift.
+	pub fn ids(&self) -> HashSet<Id> {
+		self.logs
+			.iter()
+			.filter_map(|log| match log.event {
+				Event::Shift(id) => Some(id),
+				_ => None,
+			})
+			.collect()
+	}
+
+	fn shifts(&self, id: Id) -> impl Iterator<Item = &Log> {
+		let mut is_match = false;
+
+		self.logs.iter().filter(move |log| {
+			if let Event::Shift(shift_id) = log.event {
+				is_match = id == shift_id;
+			}
+
+			is_match
+		})
+	}
+
+	// This only works because awake/asleep come in perfect pairs
+	fn sleeps(
+		&self,
+		id: Id,
+	) -> impl Iterator<Item = (DateTime<Utc>, DateTime<Utc>)> + '_ {
+		let wakes = self
+			.shifts(id)
+			.filter(|log| log.event == Event::Wake)
+			.map(|log| log.timestamp);
+
+		self.shifts(id)
+			.filter(|log| log.event == Event::Asleep)
+			.map(|log| log.timestamp)
+			.zip(wakes)
+	}
+
+	/// Total milliseconds the guard spent asleep.
+	pub fn total_asleep(&self, id: Id) -> i64 {
+		self.sleeps(id).fold(0, |acc, (start, end)| {
+			let duration = end.signed_duration_since(start).num_milliseconds();
+
+			acc.saturating_add(duration)
+		})
+	}
+
+	/// Guard that spent the most time asleep.
+	pub fn max_asleep(&self) -> Id {
+		self.ids()
+			.into_iter()
+			.map(|id| (id, self.total_asleep(id)))
+			.max_by_key(|(_, total)| *total)
+			.map(|(id, _)| id)
+			.expect("Expected someone to sleep")
+	}
+
+	/// Minute the guard was most often asleep and how many times they were
+	/// asleep during it.
+	pub fn max_minute_asleep(&self, id: Id) -> (usize, u32) {
+		// Sleeps spanning whole hours count once for each of them
+		let minutes =
+			self.sleeps(id).fold([0u32; 60], |mut acc, (start, end)| {
+				let minute = start.time().minute() as usize;
+				let duration =
+					end.signed_duration_since(start).num_minutes().max(0);
+				let hours = u32::try_from(duration / 60).unwrap_or(u32::MAX);
+
+				for (i, count) in acc.iter_mut().enumerate() {
+					let partial =
+						(i + 60 - minute) % 60 < (duration % 60) as usize;
+
+					*count = count
+						.saturating_add(hours)
+						.saturating_add(partial as u32);
+				}
+
+				acc
+			});
+
+		minutes
+			.iter()
+			.enumerate()
+			.max_by_key(|(_, n)| *n)
+			.map(|(i, n)| (i, *n))
+			.expect("Expected at least one sleep")
+	}
+
+	/// Guard most frequently asleep on the same minute and that minute.
+	pub fn max_frequency_asleep(&self) -> (Id, usize) {
+		let (id, (minute, _)) = self
+			.ids()
+			.into_iter()
+			.map(|id| (id, self.max_minute_asleep(id)))
+			.max_by_key(|(_, (_, count))| *count)
+			.expect("Expected at least one sleep");
+
+		(id, minute)
+	}
+}
+
+impl FromStr for Day04 {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let logs = parse::parse_lines(s, str::parse::<Log>)?;
+
+		// Sleeping and waking only make sense once a guard is on shift
+		let (first, log) = logs
+			.iter()
+			.enumerate()
+			.min_by_key(|(_, log)| log.timestamp)
+			.ok_or(ParseError::Empty)?;
+
+		if let Event::Shift(_) = log.event {
+			return Ok(Day04::new(logs));
+		}
+
+		let (number, line) = parse::lines(s).nth(first).unwrap_or_default();
+		let event = line[(line.find(']').unwrap_or(0) + 1)..].trim();
+
+		Err(
+			ParseError::invalid(line, event, "a guard beginning a shift")
+				.on_line(number),
+		)
+	}
+}
+
+impl Solution for Day04 {
+	const DAY: u32 = 4;
+
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<usize> {
+		let guard = self.max_asleep();
+		let (minute, _) = self.max_minute_asleep(guard);
+
+		Ok(guard * minute)
+	}
+
+	fn part2(&self) -> common::Result<usize> {
+		let (id, minute) = self.max_frequency_asleep();
+
+		Ok(id * minute)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::NaiveDate;
+	use proptest::prelude::*;
+
+	static TEST_INPUT: &str = r#"
+		[1518-11-01 00:00] Guard #10 begins shift
+		[1518-11-01 00:05] falls asleep
+		[1518-11-01 00:25] wakes up
+		[1518-11-01 00:30] falls asleep
+		[1518-11-01 00:55] wakes up
+		[1518-11-01 23:58] Guard #99 begins shift
+		[1518-11-02 00:40] falls asleep
+		[1518-11-02 00:50] wakes up
+		[1518-11-03 00:05] Guard #10 begins shift
+		[1518-11-03 00:24] falls asleep
+		[1518-11-03 00:29] wakes up
+		[1518-11-04 00:02] Guard #99 begins shift
+		[1518-11-04 00:36] falls asleep
+		[1518-11-04 00:46] wakes up
+		[1518-11-05 00:03] Guard #99 begins shift
+		[1518-11-05 00:45] falls asleep
+		[1518-11-05 00:55] wakes up
+	"#;
+
+	#[test]
+	fn ids() {
+		let day: Day04 = TEST_INPUT.parse().unwrap();
+		let mut ids = day.ids().into_iter().collect::<Vec<_>>();
+
+		ids.sort();
+
+		assert_eq!(ids, vec![10, 99]);
+	}
+
+	#[test]
+	fn max_minute() {
+		let day: Day04 = TEST_INPUT.parse().unwrap();
+		let guard = day.max_asleep();
+		let (minute, count) = day.max_minute_asleep(guard);
+
+		assert_eq!(guard, 10);
+		assert_eq!(minute, 24);
+		assert_eq!(count, 2);
+	}
+
+	#[test]
+	fn max_minute_long_sleep() {
+		let day: Day04 = r#"
+			[1518-11-01 00:00] Guard #10 begins shift
+			[1518-11-01 00:05] falls asleep
+			[1519-11-01 00:07] wakes up
+		"#
+		.parse()
+		.unwrap();
+
+		assert_eq!(day.max_minute_asleep(10), (6, 8761));
+	}
+
+	#[test]
+	fn max_frequency() {
+		let day: Day04 = TEST_INPUT.parse().unwrap();
+		let (id, minute) = day.max_frequency_asleep();
+
+		assert_eq!(id, 99);
+		assert_eq!(minute, 45);
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"[1518-11-01 00:00] Guard #10 begins shift\n[1518-11-01 00:05] dozes"
+				.parse::<Day04>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 20,
+				text: "dozes".to_owned(),
+				expected: "a guard event",
+			})
+		);
+
+		assert_eq!(
+			"[1518-11-01 00:00] Guard #x begins shift".parse::<Day04>(),
+			Err(ParseError::Invalid {
+				line: 1,
+				column: 27,
+				text: "x".to_owned(),
+				expected: "a guard id",
+			})
+		);
+
+		assert_eq!(
+			"[1518-11-01 00:05] wakes up
+[1518-11-01 00:00] falls asleep"
+				.parse::<Day04>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 20,
+				text: "falls asleep".to_owned(),
+				expected: "a guard beginning a shift",
+			})
+		);
+	}
+
+	fn event() -> impl Strategy<Value = Event> {
+		prop_oneof![
+			any::<Id>().prop_map(Event::Shift),
+			Just(Event::Asleep),
+			Just(Event::Wake),
+		]
+	}
+
+	// Logs are only precise to the minute
+	fn timestamp() -> impl Strategy<Value = DateTime<Utc>> {
+		(0..=9999i32, 1..=12u32, 1..=28u32, 0..24u32, 0..60u32).prop_map(
+			|(year, month, day, hour, minute)| {
+				let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+
+				Utc.from_utc_datetime(
+					&date.and_hms_opt(hour, minute, 0).unwrap(),
+				)
+			},
+		)
+	}
+
+	proptest! {
+		#[test]
+		fn log_round_trip(timestamp in timestamp(), event in event()) {
+			let log = Log { timestamp, event };
+
+			prop_assert_eq!(log.to_string().parse(), Ok(log));
+		}
+	}
+}
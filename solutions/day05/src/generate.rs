@@ -0,0 +1,114 @@
+//! Random polymers with known answers
+
+use common::generate::{Generated, Rng};
+
+fn reacts(a: char, b: char) -> bool {
+	a != b && a.eq_ignore_ascii_case(&b)
+}
+
+fn opposite(unit: char) -> char {
+	if unit.is_ascii_lowercase() {
+		unit.to_ascii_uppercase()
+	} else {
+		unit.to_ascii_lowercase()
+	}
+}
+
+fn unit(rng: &mut Rng) -> char {
+	let unit = rng.letter();
+
+	if rng.one_in(2) {
+		unit
+	} else {
+		opposite(unit)
+	}
+}
+
+// Length of `units` after removing every unit of type `removed` and reacting
+fn reacted_len(units: &[char], removed: char) -> usize {
+	units
+		.iter()
+		.filter(|unit| !unit.eq_ignore_ascii_case(&removed))
+		.fold(Vec::new(), |mut stack, &unit| {
+			match stack.last() {
+				Some(&last) if reacts(last, unit) => {
+					stack.pop();
+				}
+				_ => stack.push(unit),
+			}
+
+			stack
+		})
+		.len()
+}
+
+/// Generates a polymer of `n` units. Units that survive the reaction are
+/// interleaved with nested blocks that react away entirely, e.g. `aBcCbA`.
+/// Removing a unit type commutes with reacting, so both answers follow from
+/// the surviving units alone.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<usize, usize> {
+	let mut polymer = String::with_capacity(n);
+	let mut survivors = Vec::<char>::new();
+	let mut open = Vec::new();
+
+	loop {
+		let remaining = n - polymer.len();
+
+		if !open.is_empty() && (remaining <= open.len() || rng.one_in(2)) {
+			polymer.push(opposite(open.pop().unwrap()));
+		} else if remaining == 0 {
+			break;
+		} else if open.is_empty() && (remaining == 1 || rng.one_in(4)) {
+			let unit = loop {
+				let unit = unit(rng);
+
+				match survivors.last() {
+					Some(&last) if reacts(last, unit) => {}
+					_ => break unit,
+				}
+			};
+
+			polymer.push(unit);
+			survivors.push(unit);
+		} else if remaining >= open.len() + 2 {
+			let unit = unit(rng);
+
+			polymer.push(unit);
+			open.push(unit);
+		} else {
+			polymer.push(opposite(open.pop().unwrap()));
+		}
+	}
+
+	let improved = (b'a'..=b'z')
+		.map(|removed| reacted_len(&survivors, removed as char))
+		.min();
+
+	Generated {
+		input: polymer,
+		part1: Some(survivors.len()),
+		part2: improved,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{improve_reaction, reaction};
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(5);
+
+		for n in &[1, 2, 10, 500] {
+			let generated = super::generate(&mut rng, *n);
+
+			assert_eq!(generated.input.len(), *n);
+			assert_eq!(Some(reaction(&generated.input).len()), generated.part1);
+			assert_eq!(
+				Some(improve_reaction(&generated.input).len()),
+				generated.part2
+			);
+		}
+	}
+}
@@ -0,0 +1,1012 @@
+//! Day 5: Alchemical Reduction
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::str::{self, FromStr};
+
+use common::parse::{self, ParseError};
+use common::{report, Solution};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Bytes read at a time by `Reactor::read`
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Which units annihilate when they meet and which transform into other
+/// units as they join a polymer.
+///
+/// Units either have an explicit partner to annihilate with or, when the
+/// rules follow case, annihilate with any unit of the same letter in the
+/// opposite case. Either way, everything a unit can annihilate with shares
+/// its unit type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReactionRules {
+	// Partners of ASCII units, looked up on every unit, and of the rest
+	ascii: [Option<char>; 128],
+	partners: HashMap<char, char>,
+	transforms: HashMap<char, char>,
+	case: bool,
+}
+
+// Lowercase form of `unit` that its other cases share, e.g. `σ` for `Σ` and
+// the final form `ς`, or `unit` itself when a case maps to several units
+fn fold(unit: char) -> char {
+	fn single(mut chars: impl Iterator<Item = char>) -> Option<char> {
+		match (chars.next(), chars.next()) {
+			(Some(c), None) => Some(c),
+			_ => None,
+		}
+	}
+
+	single(unit.to_uppercase())
+		.and_then(|upper| single(upper.to_lowercase()))
+		.or_else(|| single(unit.to_lowercase()))
+		.unwrap_or(unit)
+}
+
+// Whether two units are the same letter in opposite cases
+fn opposite_case(a: char, b: char) -> bool {
+	if a.is_ascii() && b.is_ascii() {
+		return a != b && a.eq_ignore_ascii_case(&b);
+	}
+
+	a.is_lowercase() != b.is_lowercase() && fold(a) == fold(b)
+}
+
+impl ReactionRules {
+	/// Rules where nothing reacts or transforms.
+	pub fn new() -> Self {
+		Self {
+			ascii: [None; 128],
+			partners: HashMap::new(),
+			transforms: HashMap::new(),
+			case: false,
+		}
+	}
+
+	/// The puzzle's rules, where every letter annihilates with itself in the
+	/// opposite case, e.g. `aA`, `éÉ` or `σΣ`.
+	pub fn puzzle() -> Self {
+		Self {
+			case: true,
+			..Self::new()
+		}
+	}
+
+	// Makes `a` and `b` partners, unless either already has one
+	fn add_pair(&mut self, a: char, b: char) -> bool {
+		if self.partner(a).is_some() || self.partner(b).is_some() {
+			return false;
+		}
+
+		for &(unit, partner) in &[(a, b), (b, a)] {
+			match self.ascii.get_mut(unit as usize) {
+				Some(slot) => *slot = Some(partner),
+				None => {
+					self.partners.insert(unit, partner);
+				}
+			}
+		}
+
+		true
+	}
+
+	// Adds the rule on a line of a rule file
+	fn add_rule(&mut self, line: &str) -> Result<(), ParseError> {
+		let tokens = line.split_whitespace().collect::<Vec<_>>();
+		let added = match tokens[..] {
+			["case"] => !std::mem::replace(&mut self.case, true),
+			[a, b] => self.add_pair(unit(line, a)?, unit(line, b)?),
+			[from, "->", to] => self
+				.transforms
+				.insert(unit(line, from)?, unit(line, to)?)
+				.is_none(),
+			_ => {
+				return Err(ParseError::invalid(
+					line,
+					line.trim(),
+					"`<unit> <unit>`, `<unit> -> <unit>` or `case`",
+				))
+			}
+		};
+
+		if !added {
+			let expected = match tokens[..] {
+				["case"] => "a rule that isn't repeated",
+				[_, "->", _] => "a unit without a transform",
+				_ => "units without a partner",
+			};
+
+			return Err(ParseError::invalid(line, line.trim(), expected));
+		}
+
+		Ok(())
+	}
+
+	/// The unit explicitly given to annihilate with `unit`, if any.
+	pub fn partner(&self, unit: char) -> Option<char> {
+		match self.ascii.get(unit as usize) {
+			Some(&partner) => partner,
+			None => self.partners.get(&unit).copied(),
+		}
+	}
+
+	/// Whether `a` and `b` annihilate when `b` follows `a`. Units with an
+	/// explicit partner only annihilate with it.
+	pub fn reacts(&self, a: char, b: char) -> bool {
+		match (self.partner(a), self.partner(b)) {
+			(None, None) => self.case && opposite_case(a, b),
+			(partner, _) => partner == Some(b),
+		}
+	}
+
+	/// What `unit` becomes as it joins a polymer.
+	pub fn transform(&self, unit: char) -> char {
+		self.transforms.get(&unit).copied().unwrap_or(unit)
+	}
+
+	/// Unit type of `unit`, shared with everything it annihilates with.
+	pub fn unit_type(&self, unit: char) -> char {
+		match self.partner(unit) {
+			Some(partner) => unit.min(partner),
+			None if self.case && unit.is_ascii() => unit.to_ascii_lowercase(),
+			None if self.case => fold(unit),
+			None => unit,
+		}
+	}
+
+	// Adds each unit to the end of an already reacted polymer, reacting it
+	// with the last unit as it goes
+	fn react(&self, polymer: &mut String, units: impl Iterator<Item = char>) {
+		for unit in units {
+			match polymer.chars().next_back() {
+				Some(last) if self.reacts(last, unit) => {
+					polymer.pop();
+				}
+				_ => polymer.push(unit),
+			}
+		}
+	}
+
+	// Transforms and fully reacts `initial`
+	fn reacted(&self, initial: &str) -> String {
+		let mut polymer = String::with_capacity(initial.len());
+
+		self.react(&mut polymer, initial.chars().map(|c| self.transform(c)));
+
+		polymer
+	}
+
+	// Takes every unit of type `removed` out of a reacted polymer and reacts
+	// what's left into `polymer`, returning the number of units left.
+	// Removing a type never stops other units reacting, so as many are left
+	// as when it's removed before the first reaction, though not always the
+	// same ones when a unit reacts with several others, e.g. `Σ` with `σ`
+	// and `ς`.
+	fn without(
+		&self,
+		reacted: &str,
+		removed: char,
+		polymer: &mut String,
+	) -> usize {
+		let units = reacted
+			.chars()
+			.filter(|&unit| self.unit_type(unit) != removed);
+
+		polymer.clear();
+		self.react(polymer, units);
+		polymer.chars().count()
+	}
+
+	// Units left after removing each type in a reacted polymer, in order, in
+	// parallel with the `parallel` feature. Only types left after reacting
+	// can shorten the polymer. Each worker reacts into a single buffer.
+	fn improvements_of(&self, reacted: &str) -> Vec<(char, usize)> {
+		let candidates = reacted
+			.chars()
+			.map(|unit| self.unit_type(unit))
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect::<Vec<_>>();
+
+		#[cfg(not(feature = "parallel"))]
+		let lens = {
+			let mut polymer = String::new();
+
+			candidates
+				.iter()
+				.map(|&removed| self.without(reacted, removed, &mut polymer))
+				.collect::<Vec<_>>()
+		};
+
+		#[cfg(feature = "parallel")]
+		let lens = candidates
+			.par_iter()
+			.map_init(String::new, |polymer, &removed| {
+				self.without(reacted, removed, polymer)
+			})
+			.collect::<Vec<_>>();
+
+		candidates.into_iter().zip(lens).collect()
+	}
+
+	/// Transforms and then fully reacts a polymer, repeatedly removing
+	/// adjacent units that annihilate, and returns what remains.
+	pub fn reaction(&self, initial: &str) -> String {
+		self.reacted(initial)
+	}
+
+	/// Each unit type left after reacting a polymer, in order, with the
+	/// number of units that remain if it's removed before reacting.
+	pub fn improvements(&self, initial: &str) -> Vec<(char, usize)> {
+		self.improvements_of(&self.reacted(initial))
+	}
+
+	// Type whose removal leaves the fewest units in a reacted polymer, the
+	// first of any tie, along with how many are left
+	fn best_improvement(&self, reacted: &str) -> Option<(char, usize)> {
+		self.improvements_of(reacted)
+			.into_iter()
+			.min_by_key(|&(_, len)| len)
+	}
+
+	/// Shortest polymer obtainable by removing every unit of a single type,
+	/// once transformed, before reacting. Ties go to the first type.
+	pub fn improve_reaction(&self, initial: &str) -> String {
+		let units = initial.chars().map(|c| self.transform(c));
+		let mut polymer = String::with_capacity(initial.len());
+
+		match self.best_improvement(&self.reacted(initial)) {
+			Some((removed, _)) => self.react(
+				&mut polymer,
+				units.filter(|&unit| self.unit_type(unit) != removed),
+			),
+			None => self.react(&mut polymer, units),
+		}
+
+		polymer
+	}
+
+	/// Number of units in the polymer
+	/// [`improve_reaction`](Self::improve_reaction) gives, for a polymer
+	/// that has already been reacted, e.g. by
+	/// [`react_reader`](Self::react_reader), so isn't transformed again.
+	/// Only the number is known without the original polymer, as the units
+	/// left can differ when a unit reacts with several others.
+	pub fn improved_len(&self, reacted: &str) -> usize {
+		match self.best_improvement(reacted) {
+			Some((_, len)) => len,
+			None => reacted.chars().count(),
+		}
+	}
+
+	/// Reacts a polymer read from `input` a chunk at a time, holding only
+	/// the units that haven't reacted yet, and returns what remains. See
+	/// [`Reactor::read`].
+	pub fn react_reader(&self, input: impl Read) -> io::Result<String> {
+		let mut reactor = Reactor::new(self);
+
+		reactor.read(input)?;
+
+		Ok(reactor.into_polymer())
+	}
+
+	/// Reacts a polymer like [`reaction`](Self::reaction), recording every
+	/// annihilation along the way, and works out the
+	/// [`improvements`](Self::improvements).
+	pub fn trace(&self, initial: &str) -> Trace {
+		// Units left so far with their offsets and the deepest annihilation
+		// since each was added
+		let mut polymer = Vec::<(char, usize, Option<usize>)>::new();
+		let mut annihilations = Vec::new();
+		let mut removed = BTreeMap::new();
+
+		for (offset, unit) in
+			initial.chars().map(|c| self.transform(c)).enumerate()
+		{
+			match polymer.last() {
+				Some(&(last, start, inner)) if self.reacts(last, unit) => {
+					let depth = inner.map_or(0, |depth| depth + 1);
+
+					polymer.pop();
+
+					if let Some(outer) = polymer.last_mut() {
+						outer.2 = outer.2.max(Some(depth));
+					}
+
+					*removed.entry(self.unit_type(unit)).or_insert(0) += 2;
+					annihilations.push(Annihilation {
+						offsets: (start, offset),
+						units: (last, unit),
+						depth,
+					});
+				}
+				_ => polymer.push((unit, offset, None)),
+			}
+		}
+
+		let reacted = polymer
+			.into_iter()
+			.map(|(unit, ..)| unit)
+			.collect::<String>();
+
+		Trace {
+			annihilations,
+			removed,
+			improvements: self.improvements_of(&reacted),
+			polymer: reacted,
+		}
+	}
+}
+
+/// Reacts a polymer fed to it a piece at a time, holding only the units that
+/// haven't reacted yet.
+#[derive(Clone, Debug)]
+pub struct Reactor<'a> {
+	rules: &'a ReactionRules,
+	polymer: String,
+	len: usize,
+}
+
+impl<'a> Reactor<'a> {
+	/// Creates a reactor with nothing fed to it yet.
+	pub fn new(rules: &'a ReactionRules) -> Self {
+		Self {
+			rules,
+			polymer: String::new(),
+			len: 0,
+		}
+	}
+
+	/// Transforms `unit` and adds it to the end of the polymer, reacting it
+	/// with the last unit left.
+	pub fn push(&mut self, unit: char) {
+		let unit = self.rules.transform(unit);
+
+		match self.polymer.chars().next_back() {
+			Some(last) if self.rules.reacts(last, unit) => {
+				self.polymer.pop();
+				self.len -= 1;
+			}
+			_ => {
+				self.polymer.push(unit);
+				self.len += 1;
+			}
+		}
+	}
+
+	/// Adds every unit of `units` in turn.
+	pub fn feed(&mut self, units: &str) {
+		units.chars().for_each(|unit| self.push(unit));
+	}
+
+	/// Adds every unit read from `input`, a chunk at a time. Whitespace
+	/// around the units, such as a trailing newline, is skipped. Fails on
+	/// anything that isn't UTF-8 or on whitespace between units, having
+	/// added the units before it.
+	pub fn read(&mut self, mut input: impl Read) -> io::Result<()> {
+		let mut buffer = vec![0; CHUNK_SIZE];
+		// Bytes of a character split across chunks, kept at the start of
+		// the buffer
+		let mut pending = 0;
+		let mut started = false;
+		let mut ended = false;
+
+		loop {
+			let filled = match input.read(&mut buffer[pending..]) {
+				Ok(0) => break,
+				Ok(read) => pending + read,
+				Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+					continue
+				}
+				Err(err) => return Err(err),
+			};
+
+			// The units before invalid UTF-8 are still added, while a
+			// character split across chunks is finished by the next read
+			let (chunk, invalid) = match str::from_utf8(&buffer[..filled]) {
+				Ok(chunk) => (chunk, None),
+				Err(err) => {
+					let chunk = str::from_utf8(&buffer[..err.valid_up_to()])
+						.expect("Expected the start of the chunk to be UTF-8");
+
+					(chunk, err.error_len().map(|_| err))
+				}
+			};
+
+			for unit in chunk.chars() {
+				if unit.is_whitespace() {
+					ended = started;
+				} else if ended {
+					return Err(io::Error::new(
+						io::ErrorKind::InvalidData,
+						"expected a polymer unit, found whitespace",
+					));
+				} else {
+					started = true;
+					self.push(unit);
+				}
+			}
+
+			if let Some(err) = invalid {
+				return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+			}
+
+			let used = chunk.len();
+
+			buffer.copy_within(used..filled, 0);
+			pending = filled - used;
+		}
+
+		if pending > 0 {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				"polymer ends part way through a character",
+			));
+		}
+
+		Ok(())
+	}
+
+	/// Number of units left so far.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Whether every unit so far has reacted.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Units left so far.
+	pub fn polymer(&self) -> &str {
+		&self.polymer
+	}
+
+	/// Writes the units left so far to `out`.
+	pub fn write_polymer(&self, out: &mut impl Write) -> io::Result<()> {
+		out.write_all(self.polymer.as_bytes())
+	}
+
+	/// Units left once everything has been fed.
+	pub fn into_polymer(self) -> String {
+		self.polymer
+	}
+}
+
+/// Two units annihilating during a reaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annihilation {
+	/// Character offsets of both units in the original polymer
+	pub offsets: (usize, usize),
+	/// Both units, once transformed
+	pub units: (char, char),
+	/// Zero for units that were next to each other to begin with, otherwise
+	/// one more than the deepest annihilation between them
+	pub depth: usize,
+}
+
+/// How a polymer reacted, see [`ReactionRules::trace`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+	/// Every annihilation, in the order they happened
+	pub annihilations: Vec<Annihilation>,
+	/// What remains after reacting
+	pub polymer: String,
+	/// Number of units of each type that annihilated
+	pub removed: BTreeMap<char, usize>,
+	/// Units left when each type is removed before reacting, see
+	/// [`ReactionRules::improvements`]
+	pub improvements: Vec<(char, usize)>,
+}
+
+impl Trace {
+	/// The trace as a single line of JSON.
+	pub fn to_json(&self) -> String {
+		let unit = |unit: char| report::json_string(&unit.to_string());
+		let annihilations = self
+			.annihilations
+			.iter()
+			.map(|a| {
+				format!(
+					r#"{{"offsets":[{},{}],"units":[{},{}],"depth":{}}}"#,
+					a.offsets.0,
+					a.offsets.1,
+					unit(a.units.0),
+					unit(a.units.1),
+					a.depth
+				)
+			})
+			.collect::<Vec<_>>();
+		let removed = self
+			.removed
+			.iter()
+			.map(|(&unit_type, count)| format!("{}:{}", unit(unit_type), count))
+			.collect::<Vec<_>>();
+		let improvements = self
+			.improvements
+			.iter()
+			.map(|&(unit_type, len)| format!("{}:{}", unit(unit_type), len))
+			.collect::<Vec<_>>();
+
+		format!(
+			r#"{{"annihilations":[{}],"polymer":{},"removed":{{{}}},"improvements":{{{}}}}}"#,
+			annihilations.join(","),
+			report::json_string(&self.polymer),
+			removed.join(","),
+			improvements.join(",")
+		)
+	}
+}
+
+/// Writes one line per annihilation followed by the units each type lost
+/// and the units left when removing each type.
+impl fmt::Display for Trace {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, a) in self.annihilations.iter().enumerate() {
+			writeln!(
+				f,
+				"{}: {} at {} and {} at {} annihilate (depth {})",
+				i + 1,
+				a.units.0,
+				a.offsets.0,
+				a.units.1,
+				a.offsets.1,
+				a.depth
+			)?;
+		}
+
+		let list = |pairs: &mut dyn Iterator<Item = (char, usize)>| {
+			pairs
+				.map(|(unit_type, n)| format!("{} {}", unit_type, n))
+				.collect::<Vec<_>>()
+				.join(", ")
+		};
+
+		writeln!(f, "Units left: {}", self.polymer.chars().count())?;
+		writeln!(
+			f,
+			"Units removed by type: {}",
+			list(&mut self.removed.iter().map(|(&t, &n)| (t, n)))
+		)?;
+		write!(
+			f,
+			"Units left without each type: {}",
+			list(&mut self.improvements.iter().copied())
+		)
+	}
+}
+
+impl Default for ReactionRules {
+	fn default() -> Self {
+		Self::puzzle()
+	}
+}
+
+// A single unit, reporting anything longer against `line`
+fn unit(line: &str, token: &str) -> Result<char, ParseError> {
+	let mut chars = token.chars();
+
+	match (chars.next(), chars.next()) {
+		(Some(unit), None) => Ok(unit),
+		_ => Err(ParseError::invalid(line, token, "a single unit")),
+	}
+}
+
+impl FromStr for ReactionRules {
+	type Err = ParseError;
+
+	/// Parses one rule per line, either two units that annihilate (`a A`),
+	/// a unit that transforms into another (`x -> y`) or `case` for letters
+	/// to annihilate with themselves in the opposite case. Lines starting
+	/// with `#` are comments.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let mut rules = Self::new();
+		let lines = parse::lines(s)
+			.filter(|(_, line)| !line.trim_start().starts_with('#'));
+
+		for (number, line) in lines {
+			rules.add_rule(line).map_err(|err| err.on_line(number))?;
+		}
+
+		if rules == Self::new() {
+			return Err(ParseError::Empty);
+		}
+
+		Ok(rules)
+	}
+}
+
+/// Fully reacts a polymer, repeatedly removing adjacent units of the same
+/// type and opposite polarity (e.g. `aA`), and returns what remains (part 1).
+pub fn reaction(initial: &str) -> String {
+	ReactionRules::puzzle().reaction(initial)
+}
+
+/// Shortest polymer obtainable by removing every unit of a single type
+/// before reacting (part 2).
+pub fn improve_reaction(s: &str) -> String {
+	ReactionRules::puzzle().improve_reaction(s)
+}
+
+/// A polymer made of units, e.g. `dabAcCaCBAcCcaDA`.
+#[derive(Debug, PartialEq)]
+pub struct Day05 {
+	polymer: String,
+}
+
+impl Day05 {
+	/// Creates a puzzle from a polymer.
+	pub fn new<S: Into<String>>(polymer: S) -> Self {
+		Self {
+			polymer: polymer.into(),
+		}
+	}
+
+	/// The unreacted polymer
+	pub fn polymer(&self) -> &str {
+		&self.polymer
+	}
+}
+
+impl fmt::Display for Day05 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.polymer)
+	}
+}
+
+impl FromStr for Day05 {
+	type Err = ParseError;
+
+	/// Parses a polymer from a single line. Any character other than
+	/// whitespace is a unit, so rules other than the puzzle's can use them.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		parse::single_line(s, |line| {
+			let polymer = line.trim();
+
+			if let Some((i, c)) =
+				polymer.char_indices().find(|(_, c)| c.is_whitespace())
+			{
+				let unit = &polymer[i..(i + c.len_utf8())];
+
+				return Err(ParseError::invalid(line, unit, "a polymer unit"));
+			}
+
+			Ok(Day05::new(polymer))
+		})
+	}
+}
+
+impl Solution for Day05 {
+	const DAY: u32 = 5;
+
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<usize> {
+		Ok(reaction(&self.polymer).chars().count())
+	}
+
+	fn part2(&self) -> common::Result<usize> {
+		Ok(improve_reaction(&self.polymer).chars().count())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	#[test]
+	fn reaction() {
+		assert_eq!(super::reaction("dabAcCaCBAcCcaDA"), "dabCBAcaDA");
+		assert_eq!(super::reaction("abcdDCBA"), "");
+		assert_eq!(super::reaction("aAbaBA"), "baBA");
+		assert_eq!(super::reaction("aa"), "aa");
+		assert_eq!(super::reaction(""), "");
+
+		// Letters outside ASCII react by case too
+		assert_eq!(super::reaction("éÉxαβΒΑ"), "x");
+		assert_eq!(super::reaction("σΣςΣ"), "");
+		assert_eq!(super::reaction("ßẞ"), "");
+		assert_eq!(super::reaction("σς1"), "σς1");
+
+		let long = "aBcD".repeat(250_000) + &"dCbA".repeat(250_000);
+
+		assert_eq!(super::reaction(&long), "");
+	}
+
+	#[test]
+	fn improve_reaction() {
+		assert_eq!(super::improve_reaction("dabAcCaCBAcCcaDA"), "daDA");
+		assert_eq!(super::improve_reaction("aA"), "");
+		assert_eq!(super::improve_reaction("αγΑδ"), "δ");
+
+		// Removing `c` lets the `bB` either side of it react
+		assert_eq!(super::improve_reaction("xbcBx"), "xx");
+
+		// `Σ` reacts with both `σ` and `ς`, so removing `x` from the reacted
+		// `σxxx` leaves `σ`, where removing it first leaves `ς`
+		let rules = ReactionRules::puzzle();
+
+		assert_eq!(rules.reaction("σxΣςxx"), "σxxx");
+		assert_eq!(rules.improve_reaction("σxΣςxx"), "ς");
+		assert_eq!(rules.improved_len("σxxx"), 1);
+	}
+
+	// Reads a byte at a time, splitting every multi-byte character
+	struct Trickle<'a>(&'a [u8]);
+
+	impl Read for Trickle<'_> {
+		fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+			match self.0.split_first() {
+				Some((&byte, rest)) if !buf.is_empty() => {
+					buf[0] = byte;
+					self.0 = rest;
+
+					Ok(1)
+				}
+				_ => Ok(0),
+			}
+		}
+	}
+
+	#[test]
+	fn reactor() {
+		let rules = ReactionRules::puzzle();
+		let mut reactor = Reactor::new(&rules);
+
+		reactor.feed("dabAc");
+		reactor.feed("CaCBA");
+
+		assert_eq!(reactor.polymer(), "dabCBA");
+		assert_eq!(reactor.len(), 6);
+
+		reactor.feed("abcd");
+
+		assert_eq!(reactor.len(), 4);
+
+		let mut out = Vec::new();
+
+		reactor.write_polymer(&mut out).unwrap();
+
+		assert_eq!(out, b"dabd");
+		assert_eq!(reactor.into_polymer(), "dabd");
+	}
+
+	#[test]
+	fn reactor_read() {
+		let rules = ReactionRules::puzzle();
+		let mut reactor = Reactor::new(&rules);
+
+		reactor.read(Trickle(b"dabAc\n")).unwrap();
+		reactor.read(Trickle(b"CaCBA")).unwrap();
+
+		assert_eq!(reactor.polymer(), "dabCBA");
+
+		// Units before the error stay fed
+		let error = reactor.read(&b"c a"[..]).unwrap_err();
+
+		assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(reactor.polymer(), "dabCBAc");
+
+		// Even when the error is invalid UTF-8 part way through a chunk
+		let error = reactor.read(&b"de\xffF"[..]).unwrap_err();
+
+		assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+		assert_eq!(reactor.polymer(), "dabCBAcde");
+	}
+
+	#[test]
+	fn react_reader() {
+		let rules = ReactionRules::puzzle();
+		let react = |bytes: &[u8]| rules.react_reader(Trickle(bytes));
+
+		assert_eq!(react(b"\ndabAcCaCBAcCcaDA\n").unwrap(), "dabCBAcaDA");
+		assert_eq!(react("éxXÉαβ".as_bytes()).unwrap(), "αβ");
+		assert_eq!(react(b"").unwrap(), "");
+
+		let long = "aBcD".repeat(100_000) + &"dCbA".repeat(99_999);
+
+		assert_eq!(rules.react_reader(long.as_bytes()).unwrap(), "aBcD");
+
+		for invalid in &[
+			&b"ab cd"[..],
+			b"ab\xff",
+			"aé".as_bytes().split_last().unwrap().1,
+		] {
+			assert_eq!(
+				react(invalid).unwrap_err().kind(),
+				io::ErrorKind::InvalidData
+			);
+		}
+
+		// Only the reacted polymer is transformed
+		let rules: ReactionRules = "case\na -> b\nb -> c".parse().unwrap();
+		let reacted = rules.react_reader(&b"abBa"[..]).unwrap();
+
+		assert_eq!(reacted, "bc");
+		assert_eq!(rules.improved_len(&reacted), 1);
+		assert_eq!(rules.improve_reaction("abBa"), "c");
+	}
+
+	#[test]
+	fn trace() {
+		let rules = ReactionRules::puzzle();
+		let trace = rules.trace("dabAcCaCBAcCcaDA");
+
+		assert_eq!(trace.polymer, "dabCBAcaDA");
+		assert_eq!(
+			trace.annihilations,
+			[
+				Annihilation {
+					offsets: (4, 5),
+					units: ('c', 'C'),
+					depth: 0,
+				},
+				Annihilation {
+					offsets: (3, 6),
+					units: ('A', 'a'),
+					depth: 1,
+				},
+				Annihilation {
+					offsets: (10, 11),
+					units: ('c', 'C'),
+					depth: 0,
+				},
+			]
+		);
+		assert_eq!(
+			trace.removed.into_iter().collect::<Vec<_>>(),
+			[('a', 2), ('c', 4)]
+		);
+		assert_eq!(
+			trace.improvements,
+			[('a', 6), ('b', 8), ('c', 4), ('d', 6)]
+		);
+		assert_eq!(rules.improvements("dabAcCaCBAcCcaDA"), trace.improvements);
+
+		// Depth counts the deepest cascade between the two units
+		let trace = rules.trace("abBcdDCA");
+		let depths = trace
+			.annihilations
+			.iter()
+			.map(|a| a.depth)
+			.collect::<Vec<_>>();
+
+		assert_eq!(depths, [0, 0, 1, 2]);
+	}
+
+	#[test]
+	fn trace_output() {
+		let trace = ReactionRules::puzzle().trace("xaAé\"");
+
+		assert_eq!(
+			trace.to_string(),
+			"1: a at 1 and A at 2 annihilate (depth 0)\n\
+			 Units left: 3\n\
+			 Units removed by type: a 2\n\
+			 Units left without each type: \" 2, x 2, é 2"
+		);
+		assert_eq!(
+			trace.to_json(),
+			r#"{"annihilations":[{"offsets":[1,2],"units":["a","A"],"depth":0}],"polymer":"xé\"","removed":{"a":2},"improvements":{"\"":2,"x":2,"é":2}}"#
+		);
+	}
+
+	#[test]
+	fn rules() {
+		let rules: ReactionRules =
+			"# Greek\nα Α\nβ Β\n\nx -> α\n".parse().unwrap();
+
+		assert_eq!(rules.unit_type('α'), 'Α');
+		assert_eq!(rules.unit_type('γ'), 'γ');
+		assert_eq!(rules.partner('Α'), Some('α'));
+		assert_eq!(rules.partner('a'), None);
+		assert_eq!(rules.transform('x'), 'α');
+		assert_eq!(rules.reaction("αβΒΑγ"), "γ");
+		assert_eq!(rules.reaction("xΑaA"), "aA");
+		assert_eq!(rules.improve_reaction("αββΑΒ"), "");
+
+		// Every unit reacts with itself
+		let rules: ReactionRules = "a a".parse().unwrap();
+
+		assert_eq!(rules.reaction("aaaba"), "aba");
+
+		// Units that never react are types of their own
+		let rules: ReactionRules = "a -> b".parse().unwrap();
+
+		assert_eq!(rules.reaction("abc"), "bbc");
+		assert_eq!(rules.improve_reaction("abc"), "c");
+
+		// Explicit partners win over case
+		let rules: ReactionRules = "case\nx -> é\na B".parse().unwrap();
+
+		assert_eq!(rules.reaction("xÉaAaB"), "aA");
+		assert_eq!(rules.unit_type('É'), 'é');
+		assert_eq!(ReactionRules::default(), ReactionRules::puzzle());
+	}
+
+	#[test]
+	fn rules_error() {
+		assert_eq!(
+			"case\ncase".parse::<ReactionRules>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 1,
+				text: "case".to_owned(),
+				expected: "a rule that isn't repeated",
+			})
+		);
+
+		assert_eq!(
+			"# Nothing\n".parse::<ReactionRules>(),
+			Err(ParseError::Empty)
+		);
+
+		assert_eq!(
+			"a A\nab B".parse::<ReactionRules>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 1,
+				text: "ab".to_owned(),
+				expected: "a single unit",
+			})
+		);
+
+		assert_eq!(
+			"a A\nA b".parse::<ReactionRules>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 1,
+				text: "A b".to_owned(),
+				expected: "units without a partner",
+			})
+		);
+
+		assert_eq!(
+			"a => b".parse::<ReactionRules>(),
+			Err(ParseError::Invalid {
+				line: 1,
+				column: 1,
+				text: "a => b".to_owned(),
+				expected: "`<unit> <unit>`, `<unit> -> <unit>` or `case`",
+			})
+		);
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(
+			"\ndabAcCaCBAcCcaDA\n".parse(),
+			Ok(Day05::new("dabAcCaCBAcCcaDA"))
+		);
+
+		assert_eq!(
+			"dabAc CaCB".parse::<Day05>(),
+			Err(ParseError::Invalid {
+				line: 1,
+				column: 6,
+				text: " ".to_owned(),
+				expected: "a polymer unit",
+			})
+		);
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(polymer in "\\S{1,40}") {
+			let day = Day05::new(polymer);
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
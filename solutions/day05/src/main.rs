@@ -1,73 +1,125 @@
-use std::io::{self, Read};
+use std::io::Write;
+use std::process;
 
-fn remove_pair(n: usize, s: &str) -> Option<(usize, String)> {
-	for i in n..(s.len() - 1) {
-		let a = &s[i..(i + 1)];
-		let b = &s[(i + 1)..(i + 2)];
+use common::cli::{self, Args, Format, Options};
+use common::report::Report;
+use day05::{Day05, ReactionRules, Reactor};
 
-		if a != b && a.to_uppercase() == b.to_uppercase() {
-			let prefix = &s[..i];
-			let postfix = &s[(i + 2)..];
-			let result = format!("{}{}", prefix, postfix);
+#[cfg(feature = "heap")]
+#[global_allocator]
+static ALLOC: common::alloc::Counting = common::alloc::Counting;
 
-			return Some((i, result));
-		}
+fn main() {
+	let args = Args::from_env(Options {
+		trace: true,
+		stream: true,
+		rules: true,
+		output: true,
+		..Options::NONE
+	});
+	let rules = match args.read_rules() {
+		Some(input) => input.text.parse().unwrap_or_else(|err| {
+			eprintln!("Failed to parse {}: {}", input.name, err);
+			process::exit(2);
+		}),
+		None => ReactionRules::puzzle(),
+	};
+
+	let mut output = args.create_output();
+
+	if args.stream {
+		stream(&args, &rules, &mut output);
+		return;
 	}
 
-	None
-}
+	// The JSON report reacts with the same rules as the text answers
+	let report = |input: &str| {
+		Report::run_with(
+			input,
+			|day: &Day05| Ok(rules.reaction(day.polymer()).chars().count()),
+			|day: &Day05| {
+				Ok(rules.improve_reaction(day.polymer()).chars().count())
+			},
+		)
+	};
 
-fn reaction(initial: &str) -> String {
-	let mut prev = initial.to_owned();
-	let mut prev_n = 0;
+	cli::run_with(&args, &args.read_inputs(), report, |input, profiler| {
+		let day: Day05 = profiler.step("parse", || input.parse())?;
 
-	while let Some((n, next)) = remove_pair(prev_n, &prev) {
-		prev = next;
-		prev_n = if n > 0 { n - 1 } else { 0 };
-	}
+		if let Some(format) = args.trace {
+			let trace = profiler.step("trace", || rules.trace(day.polymer()));
 
-	prev
-}
-
-fn improve_reaction(s: &str) -> String {
-	let lower = "abcdefghijklmnopqrstuvwxyz".chars().collect::<Vec<_>>();
-	let upper = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect::<Vec<_>>();
+			match format {
+				Format::Text => println!("{}", trace),
+				Format::Json => println!("{}", trace.to_json()),
+			}
 
-	(0..lower.len())
-		.map(|i| {
-			let a = lower[i];
-			let b = upper[i];
+			write(&mut output, &trace.polymer);
 
-			s.chars().filter(|&c| c != a && c != b).collect::<String>()
-		})
-		.map(|s| reaction(&s))
-		.min_by_key(|r| r.len())
-		.expect("Expected there to be at least one reaction")
-}
+			return Ok(());
+		}
 
-fn main() {
-	let mut buf = String::new();
+		let result =
+			profiler.step("reaction", || rules.reaction(day.polymer()));
+		let improved = profiler
+			.step("improve_reaction", || rules.improve_reaction(day.polymer()));
 
-	io::stdin()
-		.read_to_string(&mut buf)
-		.expect("Failed to read from stdin");
+		println!("Number of units: {}", result.chars().count());
+		println!("Number of improved units: {}", improved.chars().count());
 
-	let result = reaction(buf.trim());
-	let improved = improve_reaction(buf.trim());
+		write(&mut output, &result);
 
-	println!("Number of units: {}", result.len());
-	println!("Number of improved units: {}", improved.len());
+		Ok(())
+	});
 }
 
-#[cfg(test)]
-mod tests {
-	#[test]
-	fn reaction() {
-		assert_eq!(super::reaction("dabAcCaCBAcCcaDA"), "dabCBAcaDA");
-	}
+// Reacts each input a chunk at a time instead of reading it all first, so
+// only the units left unreacted are ever held in memory. Each polymer is
+// flushed as soon as it's written, as a failing input exits without
+// returning.
+fn stream(
+	args: &Args,
+	rules: &ReactionRules,
+	output: &mut Option<Box<dyn Write>>,
+) {
+	cli::stream(args, |reader, profiler| {
+		let mut reactor = Reactor::new(rules);
+
+		profiler.step("reaction", || reactor.read(reader))?;
+
+		let improved = profiler
+			.step("improve_reaction", || rules.improved_len(reactor.polymer()));
+
+		println!("Number of units: {}", reactor.len());
+		println!("Number of improved units: {}", improved);
+
+		// Reported here, as any I/O error left to `cli::stream` is taken
+		// for failing to read the input
+		if let Some(out) = output {
+			let written = reactor
+				.write_polymer(out)
+				.and_then(|_| writeln!(out))
+				.and_then(|_| out.flush());
+
+			if let Err(err) = written {
+				eprintln!("Failed to write the polymer: {}", err);
+				process::exit(1);
+			}
+		}
 
-	#[test]
-	fn improve_reaction() {
-		assert_eq!(super::improve_reaction("dabAcCaCBAcCcaDA"), "daDA");
+		Ok(())
+	});
+}
+
+// Writes and flushes a reacted polymer to `--output`, if given, exiting if
+// it can't be. Flushing each one keeps it when a later input fails, as that
+// exits without returning.
+fn write(output: &mut Option<Box<dyn Write>>, polymer: &str) {
+	if let Some(out) = output {
+		if let Err(err) = writeln!(out, "{}", polymer).and_then(|_| out.flush())
+		{
+			eprintln!("Failed to write the polymer: {}", err);
+			process::exit(1);
+		}
 	}
 }
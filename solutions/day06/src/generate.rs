@@ -0,0 +1,149 @@
+//! Random coordinates with known answers
+
+use std::collections::HashSet;
+
+use common::generate::{Generated, Rng};
+
+// Total distance from every coordinate that part 2 stays under
+const LIMIT: i64 = 10000;
+
+// Unique closest coordinate to `(x, y)`
+fn closest(points: &[(i64, i64)], x: i64, y: i64) -> Option<usize> {
+	let distances = points
+		.iter()
+		.map(|&(px, py)| (px - x).abs() + (py - y).abs())
+		.collect::<Vec<_>>();
+
+	let min = *distances.iter().min()?;
+	let mut nearest = distances.iter().enumerate().filter(|(_, &d)| d == min);
+
+	match (nearest.next(), nearest.next()) {
+		(Some((i, _)), None) => Some(i),
+		_ => None,
+	}
+}
+
+// Areas reaching the bounding box of the coordinates are infinite
+fn largest_area(points: &[(i64, i64)]) -> usize {
+	let (min_x, max_x) = (
+		points.iter().map(|p| p.0).min().unwrap(),
+		points.iter().map(|p| p.0).max().unwrap(),
+	);
+	let (min_y, max_y) = (
+		points.iter().map(|p| p.1).min().unwrap(),
+		points.iter().map(|p| p.1).max().unwrap(),
+	);
+
+	let mut areas = vec![Some(0); points.len()];
+
+	for x in min_x..=max_x {
+		for y in min_y..=max_y {
+			if let Some(i) = closest(points, x, y) {
+				let edge = x == min_x || x == max_x || y == min_y || y == max_y;
+
+				areas[i] = if edge { None } else { areas[i].map(|n| n + 1) };
+			}
+		}
+	}
+
+	areas.into_iter().flatten().max().unwrap_or(0)
+}
+
+// Total distance along one axis from every position that could be in the
+// region
+fn axis_distances(values: &[i64], limit: i64) -> Vec<i64> {
+	let (min, max) =
+		(*values.iter().min().unwrap(), *values.iter().max().unwrap());
+
+	(min - limit..=max + limit)
+		.map(|p| values.iter().map(|v| (v - p).abs()).sum::<i64>())
+		.filter(|&d| d < limit)
+		.collect()
+}
+
+// The total distance splits into independent X and Y sums, so the region is
+// counted by pairing each Y sum with the X sums small enough to match it
+fn region_size(points: &[(i64, i64)], limit: i64) -> usize {
+	let xs = points.iter().map(|p| p.0).collect::<Vec<_>>();
+	let ys = points.iter().map(|p| p.1).collect::<Vec<_>>();
+	let mut x_distances = axis_distances(&xs, limit);
+
+	x_distances.sort_unstable();
+
+	axis_distances(&ys, limit)
+		.into_iter()
+		.map(|dy| x_distances.partition_point(|&dx| dx + dy < limit))
+		.sum()
+}
+
+/// Generates `n` distinct coordinates spread over a square about 50 times
+/// `sqrt(n)` wide. Both answers are found by brute force over the bounding
+/// box rather than from the distance grid.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<usize, usize> {
+	let n = n.max(1);
+	let side = 50 * (n as f64).sqrt() as i64 + 10;
+	let mut seen = HashSet::new();
+	let mut points = Vec::with_capacity(n);
+
+	while points.len() < n {
+		let point = (rng.range(0, side), rng.range(0, side));
+
+		if seen.insert(point) {
+			points.push(point);
+		}
+	}
+
+	let input = points
+		.iter()
+		.map(|(x, y)| format!("{}, {}\n", x, y))
+		.collect();
+
+	Generated {
+		input,
+		part1: Some(largest_area(&points)),
+		part2: Some(region_size(&points, LIMIT)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day06;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(6);
+
+		for n in &[1, 3, 10, 40] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day06 = generated.input.parse().unwrap();
+
+			assert_eq!(Some(day.largest_area()), generated.part1);
+		}
+	}
+
+	// Part 2 of the solver is too slow to check in a debug build
+	#[test]
+	fn region_size() {
+		let mut rng = Rng::new(6);
+
+		for n in &[1, 3, 10] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day06 = generated.input.parse().unwrap();
+			let points = generated
+				.input
+				.lines()
+				.map(|line| {
+					let (x, y) = line.split_at(line.find(',').unwrap());
+
+					(x.parse().unwrap(), y[2..].parse().unwrap())
+				})
+				.collect::<Vec<_>>();
+
+			assert_eq!(
+				super::region_size(&points, 100),
+				day.close_region_size(100)
+			);
+		}
+	}
+}
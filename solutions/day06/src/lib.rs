@@ -0,0 +1,344 @@
+//! Day 6: Chronal Coordinates
+
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+use grid::Grid;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Most distances worth keeping on the grid, one per cell for each coordinate
+const MAX_DISTANCES: usize = 1 << 24;
+
+/// A location on the grid, e.g. `1, 6`.
+///
+/// Parsed coordinates are shifted by one in each direction so that the grid
+/// always has a border around the outermost coordinates.
+#[derive(Debug, PartialEq)]
+pub struct Coordinate {
+	pub x: i32,
+	pub y: i32,
+}
+
+// Coordinates are shifted by one to leave room for an empty border on the grid
+fn coordinate(
+	line: &str,
+	cursor: &mut Cursor,
+	expected: &'static str,
+) -> Result<i32, ParseError> {
+	let text = cursor.skip_whitespace().rest();
+	let value = cursor.parse::<i32, _>(|c| c.is_ascii_digit(), expected)?;
+	let text = &text[..(text.len() - cursor.rest().len())];
+
+	value
+		.checked_add(1)
+		.ok_or_else(|| ParseError::invalid(line, text, expected))
+}
+
+/// Undoes the shift applied when parsing.
+impl fmt::Display for Coordinate {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}, {}", self.x - 1, self.y - 1)
+	}
+}
+
+impl FromStr for Coordinate {
+	type Err = ParseError;
+
+	fn from_str(line: &str) -> Result<Self, ParseError> {
+		let mut cursor = Cursor::new(line);
+
+		let x = coordinate(line, &mut cursor, "an X coordinate")?;
+
+		cursor.skip_whitespace().literal(",", "`,`")?;
+
+		let y = coordinate(line, &mut cursor, "a Y coordinate")?;
+
+		cursor.end()?;
+
+		Ok(Coordinate { x, y })
+	}
+}
+
+/// Coordinates and the distance from each grid cell to each coordinate.
+#[derive(Debug, PartialEq)]
+pub struct Day06 {
+	coordinates: Vec<Coordinate>,
+	grid: Grid<Vec<i32>>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Closest {
+	distances: Grid<Option<usize>>,
+}
+
+impl Closest {
+	fn total(&self, n: usize) -> usize {
+		let mut total = 0;
+
+		for ((x, y), &closest) in self.distances.iter() {
+			if closest == Some(n) {
+				// Areas reaching the edge go on forever
+				if self.distances.is_edge(x, y) {
+					return 0;
+				}
+
+				total += 1;
+			}
+		}
+
+		total
+	}
+}
+
+// Index of the only coordinate at the smallest distance, if there's just one
+fn nearest(distances: &[i32]) -> Option<usize> {
+	distances
+		.iter()
+		.enumerate()
+		.min_by_key(|(_, d)| *d)
+		.and_then(|(i, d)| {
+			let equal = distances
+				.iter()
+				.enumerate()
+				.find(|(j, d2)| i != *j && d == *d2);
+
+			if equal.is_some() {
+				return None;
+			}
+
+			Some(i)
+		})
+}
+
+impl Day06 {
+	/// Computes the distance grid for a set of coordinates. Parsing rejects
+	/// coordinates that would need more than `MAX_DISTANCES` distances.
+	pub fn new(coordinates: Vec<Coordinate>) -> Self {
+		let width = coordinates
+			.iter()
+			.map(|point| point.x)
+			.max()
+			.expect("Expected at least one coordinate") as usize
+			+ 2;
+
+		let height = coordinates
+			.iter()
+			.map(|point| point.y)
+			.max()
+			.expect("Expected at least one coordinate") as usize
+			+ 2;
+
+		// Fill the grid with distances
+		let grid = Grid::from_fn(width, height, |i, j| {
+			coordinates
+				.iter()
+				.map(|&Coordinate { x, y }| {
+					(x - i as i32).abs() + (y - j as i32).abs()
+				})
+				.collect()
+		});
+
+		Self { coordinates, grid }
+	}
+
+	// Rows are worked out in parallel with the `parallel` feature
+	fn closest(&self) -> Closest {
+		let rows = self.grid.rows();
+
+		#[cfg(feature = "parallel")]
+		let rows = rows.collect::<Vec<_>>().into_par_iter();
+
+		let rows = rows
+			.map(|row| row.iter().map(|distances| nearest(distances)).collect())
+			.collect::<Vec<_>>();
+
+		Closest {
+			distances: Grid::from_rows(rows, None),
+		}
+	}
+
+	/// Size of the largest finite area closest to a single coordinate
+	/// (part 1).
+	pub fn largest_area(&self) -> usize {
+		let closest = Self::closest(self);
+
+		(0..self.coordinates.len())
+			.map(|n| closest.total(n))
+			.max()
+			.expect("Expected at least one coordinate")
+	}
+
+	/// Size of the region whose total distance to every coordinate is less
+	/// than `n` (part 2).
+	pub fn close_region_size(&self, n: i32) -> usize {
+		(-n..(n + self.grid.width() as i32)).fold(0, |acc, i| {
+			(-n..(n + self.grid.height() as i32))
+				.map(|j| {
+					self.coordinates.iter().fold(0, |acc, point| {
+						acc + i64::from(
+							(point.x - i).abs() + (point.y - j).abs(),
+						)
+					})
+				})
+				.filter(|&m| m < i64::from(n))
+				.count() + acc
+		})
+	}
+}
+
+/// Writes one coordinate per line.
+impl fmt::Display for Day06 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, point) in self.coordinates.iter().enumerate() {
+			if i > 0 {
+				writeln!(f)?;
+			}
+
+			write!(f, "{}", point)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl FromStr for Day06 {
+	type Err = ParseError;
+
+	/// Parses one coordinate per line, failing on the first coordinate that
+	/// takes the distance grid past `MAX_DISTANCES`.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let coordinates: Vec<Coordinate> = parse::parse_lines(s, str::parse)?;
+		let (mut width, mut height) = (0, 0);
+
+		for (n, ((number, line), point)) in
+			parse::lines(s).zip(&coordinates).enumerate()
+		{
+			width = width.max(point.x as usize + 2);
+			height = height.max(point.y as usize + 2);
+
+			if (width * height)
+				.checked_mul(n + 1)
+				.is_none_or(|distances| distances > MAX_DISTANCES)
+			{
+				let expected = "fewer coordinates closer to the origin";
+
+				return Err(ParseError::invalid(line, line.trim(), expected)
+					.on_line(number));
+			}
+		}
+
+		Ok(Self::new(coordinates))
+	}
+}
+
+impl Solution for Day06 {
+	const DAY: u32 = 6;
+
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<usize> {
+		Ok(self.largest_area())
+	}
+
+	fn part2(&self) -> common::Result<usize> {
+		Ok(self.close_region_size(10000))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
+	static TEST_INPUT: &str = r#"
+        1, 1
+        1, 6
+        8, 3
+        3, 4
+        5, 5
+        8, 9
+    "#;
+
+	#[test]
+	fn largest_area() {
+		let day: Day06 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.largest_area(), 17);
+	}
+
+	#[test]
+	fn close_region_size() {
+		let day: Day06 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.close_region_size(32), 16);
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"1, 1\n1 6".parse::<Day06>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 3,
+				text: "6".to_owned(),
+				expected: "`,`",
+			})
+		);
+
+		assert_eq!(
+			"1, 1\n2147483647, 1".parse::<Day06>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 1,
+				text: "2147483647".to_owned(),
+				expected: "an X coordinate",
+			})
+		);
+
+		assert_eq!(
+			"1, 1\n100000, 100000".parse::<Day06>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 1,
+				text: "100000, 100000".to_owned(),
+				expected: "fewer coordinates closer to the origin",
+			})
+		);
+
+		let many = "99, 99\n".repeat(2000);
+
+		assert!(many.parse::<Day06>().is_err());
+	}
+
+	proptest! {
+		#[test]
+		fn coordinate_round_trip(x in 1..=i32::MAX, y in 1..=i32::MAX) {
+			let point = Coordinate { x, y };
+
+			prop_assert_eq!(point.to_string().parse(), Ok(point));
+		}
+
+		#[test]
+		fn round_trip(points in vec((1..50, 1..50), 1..10)) {
+			let coordinates = points
+				.into_iter()
+				.map(|(x, y)| Coordinate { x, y })
+				.collect();
+			let day = Day06::new(coordinates);
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
@@ -0,0 +1,127 @@
+//! Random step dependencies with known answers
+
+use std::collections::BTreeSet;
+
+use common::generate::{Generated, Rng};
+
+// Steps as indexes into the alphabet, each with the steps it depends on
+type Steps = Vec<(u8, Vec<u8>)>;
+
+fn name(step: u8) -> char {
+	(b'A' + step) as char
+}
+
+// Steps whose dependencies are all in `done`
+fn ready(steps: &Steps, done: &[u8], started: &[u8]) -> BTreeSet<u8> {
+	steps
+		.iter()
+		.filter(|(step, _)| !started.contains(step))
+		.filter(|(_, deps)| deps.iter().all(|dep| done.contains(dep)))
+		.map(|&(step, _)| step)
+		.collect()
+}
+
+fn single_order(steps: &Steps) -> String {
+	let mut done = Vec::new();
+
+	while let Some(&next) = ready(steps, &done, &done).iter().next() {
+		done.push(next);
+	}
+
+	done.into_iter().map(name).collect()
+}
+
+// Simulates the workers one second at a time
+fn multi_worker(steps: &Steps, base_time: u32, num_workers: usize) -> u32 {
+	let mut done = Vec::new();
+	let mut started = Vec::new();
+	let mut workers = Vec::<(u8, u32)>::new();
+	let mut seconds = 0;
+
+	while done.len() < steps.len() {
+		for step in ready(steps, &done, &started) {
+			if workers.len() < num_workers {
+				workers.push((step, base_time + u32::from(step) + 1));
+				started.push(step);
+			}
+		}
+
+		seconds += 1;
+		workers.iter_mut().for_each(|(_, time)| *time -= 1);
+		done.extend(workers.iter().filter(|w| w.1 == 0).map(|w| w.0));
+		workers.retain(|w| w.1 > 0);
+	}
+
+	seconds
+}
+
+/// Generates dependencies between `n` (between 2 and 26) steps. The steps
+/// are shuffled into an order and every step after the first depends on at
+/// least one step before it, so every step is mentioned and there is never
+/// a cycle.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<String, u32> {
+	let n = n.clamp(2, 26);
+	let mut order = (0..26).collect::<Vec<u8>>();
+
+	rng.shuffle(&mut order);
+	order.truncate(n);
+
+	let steps = order
+		.iter()
+		.enumerate()
+		.map(|(i, &step)| {
+			let mut deps = order[..i]
+				.iter()
+				.copied()
+				.filter(|_| rng.one_in(4))
+				.collect::<Vec<_>>();
+
+			if i > 0 && deps.is_empty() {
+				deps.push(order[rng.below(i)]);
+			}
+
+			(step, deps)
+		})
+		.collect::<Steps>();
+
+	let mut lines = steps
+		.iter()
+		.flat_map(|(step, deps)| {
+			deps.iter().map(move |&dep| {
+				format!(
+					"Step {} must be finished before step {} can begin.",
+					name(dep),
+					name(*step)
+				)
+			})
+		})
+		.collect::<Vec<_>>();
+
+	rng.shuffle(&mut lines);
+
+	Generated {
+		input: lines.join("\n"),
+		part1: Some(single_order(&steps)),
+		part2: Some(multi_worker(&steps, 60, 5)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day07;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(7);
+
+		for n in &[2, 5, 26, 26] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day07 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+			assert_eq!(day.part2().ok(), generated.part2);
+		}
+	}
+}
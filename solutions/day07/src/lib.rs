@@ -0,0 +1,413 @@
+//! Day 7: The Sum of Its Parts
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// Step identifier, a single uppercase letter
+pub type Id = String;
+
+/// Instructions as a map from each step to the steps it depends on.
+#[derive(Debug, PartialEq)]
+pub struct Day07 {
+	dependencies: HashMap<Id, HashSet<Id>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Worker {
+	id: Option<String>,
+	time: u32,
+}
+
+impl Worker {
+	fn new() -> Self {
+		Self { id: None, time: 0 }
+	}
+}
+
+impl Day07 {
+	/// Creates instructions from a map of step to prerequisite steps. Every
+	/// step must be present as a key, even if it has no prerequisites.
+	pub fn new(dependencies: HashMap<Id, HashSet<Id>>) -> Self {
+		Self { dependencies }
+	}
+
+	fn get_extra_time(a: &str) -> u32 {
+		(a.as_bytes()[0] - 64) as u32
+	}
+
+	/// Order in which a single worker completes the steps, choosing
+	/// alphabetically among ready steps (part 1). Returns `None` if some step
+	/// can never begin, which parsing rules out.
+	pub fn single_order(&self) -> Option<String> {
+		let mut items = self.dependencies.clone();
+		let mut order = Vec::with_capacity(items.len());
+
+		while !items.is_empty() {
+			let mut ready = items
+				.iter()
+				.filter(|(_, v)| v.is_empty())
+				.map(|(id, _)| id.to_owned())
+				.collect::<Vec<_>>();
+
+			ready.sort();
+
+			let next = ready.first()?;
+
+			items.iter_mut().for_each(|(_, v)| {
+				v.remove(next);
+			});
+
+			items.remove(next);
+			order.push(next.to_owned());
+		}
+
+		Some(order.concat())
+	}
+
+	/// Seconds for `num_workers` to complete every step when each step takes
+	/// `base_time` plus its position in the alphabet (part 2). Returns `None`
+	/// if there are no workers or some step can never begin.
+	pub fn multi_worker(
+		&self,
+		base_time: u32,
+		num_workers: usize,
+	) -> Option<u32> {
+		if num_workers == 0 {
+			return None;
+		}
+
+		let mut items = self.dependencies.clone();
+		let mut workers = vec![Worker::new(); num_workers];
+		let mut remaining = items.len();
+		let mut total = 0;
+
+		while remaining > 0 {
+			let mut should_advance = true;
+
+			// Try to find a free worker
+			if let Some(worker) = workers.iter_mut().find(|w| w.id.is_none()) {
+				// Try to find available work
+				let mut ready = items
+					.iter()
+					.filter(|(_, v)| v.is_empty())
+					.map(|(id, _)| id.to_owned())
+					.collect::<Vec<_>>();
+
+				ready.sort();
+
+				// Try to find work
+				if let Some(next) = ready.first() {
+					// We might have more free workers, check before advancing
+					should_advance = false;
+
+					worker.id = Some(next.to_owned());
+					worker.time = base_time + Self::get_extra_time(next);
+					items.remove(next);
+				}
+			}
+
+			// Advance the clock, giving up if nobody is working on anything
+			if should_advance {
+				let time = workers
+					.iter()
+					.filter(|w| w.id.is_some())
+					.map(|w| w.time)
+					.min()?;
+
+				total += time;
+
+				workers.iter_mut().for_each(|w| {
+					if let Some(id) = &w.id {
+						w.time -= time;
+
+						if w.time == 0 {
+							items.iter_mut().for_each(|(_, v)| {
+								v.remove(id);
+							});
+
+							w.id = None;
+							remaining -= 1;
+						}
+					}
+				});
+			}
+		}
+
+		Some(total)
+	}
+}
+
+// A step name, which must be a single uppercase letter
+fn parse_step<'a>(
+	line: &'a str,
+	cursor: &mut Cursor<'a>,
+) -> Result<&'a str, ParseError> {
+	let step = cursor
+		.skip_whitespace()
+		.take_while(char::is_alphanumeric, "a step")?;
+
+	match step.as_bytes() {
+		[b'A'..=b'Z'] => Ok(step),
+		_ => Err(ParseError::invalid(line, step, "a step")),
+	}
+}
+
+// Step C must be finished before step A can begin.
+fn parse_pair(line: &str) -> Result<(&str, &str), ParseError> {
+	let mut cursor = Cursor::new(line);
+
+	cursor.skip_whitespace().literal("Step", "`Step`")?;
+	let dependency = parse_step(line, &mut cursor)?;
+	cursor.skip_whitespace().literal(
+		"must be finished before step",
+		"`must be finished before step`",
+	)?;
+	let id = parse_step(line, &mut cursor)?;
+	cursor
+		.skip_whitespace()
+		.literal("can begin.", "`can begin.`")?;
+	cursor.end()?;
+
+	Ok((dependency, id))
+}
+
+// Steps that can never begin because they depend on a cycle
+fn blocked(dependencies: &HashMap<Id, HashSet<Id>>) -> HashSet<Id> {
+	let mut items = dependencies.clone();
+
+	while let Some(next) = items
+		.iter()
+		.find(|(_, v)| v.is_empty())
+		.map(|(id, _)| id.to_owned())
+	{
+		items.iter_mut().for_each(|(_, v)| {
+			v.remove(&next);
+		});
+
+		items.remove(&next);
+	}
+
+	items.into_keys().collect()
+}
+
+/// Writes one dependency per line, sorted by step and then by prerequisite.
+impl fmt::Display for Day07 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut ids = self.dependencies.keys().collect::<Vec<_>>();
+		let mut first = true;
+
+		ids.sort();
+
+		for id in ids {
+			let mut dependencies =
+				self.dependencies[id].iter().collect::<Vec<_>>();
+
+			dependencies.sort();
+
+			for dependency in dependencies {
+				if !first {
+					writeln!(f)?;
+				}
+
+				first = false;
+
+				write!(
+					f,
+					"Step {} must be finished before step {} can begin.",
+					dependency, id
+				)?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl FromStr for Day07 {
+	type Err = ParseError;
+
+	/// Parses one dependency per line, failing at the first dependency that
+	/// is part of a cycle.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let pairs = parse::lines(s)
+			.map(|(number, line)| {
+				let pair =
+					parse_pair(line).map_err(|err| err.on_line(number))?;
+
+				Ok((number, line, pair))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		if pairs.is_empty() {
+			return Err(ParseError::Empty);
+		}
+
+		let mut dependencies = pairs.iter().fold(
+			HashMap::<Id, HashSet<Id>>::new(),
+			|mut acc, (_, _, (dependency, id))| {
+				acc.entry((*id).to_owned())
+					.or_default()
+					.insert((*dependency).to_owned());
+
+				acc
+			},
+		);
+
+		pairs.iter().for_each(|(_, _, (dependency, _))| {
+			dependencies.entry((*dependency).to_owned()).or_default();
+		});
+
+		let blocked = blocked(&dependencies);
+		let cycle = pairs.iter().find(|(_, _, (dependency, id))| {
+			blocked.contains(*dependency) && blocked.contains(*id)
+		});
+
+		if let Some((number, line, (dependency, _))) = cycle {
+			return Err(ParseError::invalid(
+				line,
+				dependency,
+				"a step without circular dependencies",
+			)
+			.on_line(*number));
+		}
+
+		Ok(Day07::new(dependencies))
+	}
+}
+
+impl Solution for Day07 {
+	const DAY: u32 = 7;
+
+	type Part1 = String;
+	type Part2 = u32;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<String> {
+		self.single_order()
+			.ok_or_else(|| "steps with circular dependencies".into())
+	}
+
+	fn part2(&self) -> common::Result<u32> {
+		self.multi_worker(60, 5)
+			.ok_or_else(|| "steps with circular dependencies".into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
+	static TEST_INPUT: &str = r#"
+        Step C must be finished before step A can begin.
+        Step C must be finished before step F can begin.
+        Step A must be finished before step B can begin.
+        Step A must be finished before step D can begin.
+        Step B must be finished before step E can begin.
+        Step D must be finished before step E can begin.
+        Step F must be finished before step E can begin.
+    "#;
+
+	#[test]
+	fn single_order() {
+		let day: Day07 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.single_order(), Some("CABDFE".to_owned()));
+	}
+
+	#[test]
+	fn multi_worker() {
+		let day: Day07 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.multi_worker(0, 2), Some(15));
+		assert_eq!(day.multi_worker(0, 0), None);
+	}
+
+	#[test]
+	fn circular() {
+		let day = Day07::new(
+			vec![
+				("A".to_owned(), vec!["B".to_owned()].into_iter().collect()),
+				("B".to_owned(), vec!["A".to_owned()].into_iter().collect()),
+			]
+			.into_iter()
+			.collect(),
+		);
+
+		assert_eq!(day.single_order(), None);
+		assert_eq!(day.multi_worker(0, 2), None);
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"Step C must be finished before step A can begin.\nStep C"
+				.parse::<Day07>(),
+			Err(ParseError::Missing {
+				line: 2,
+				column: 7,
+				expected: "`must be finished before step`",
+			})
+		);
+
+		assert_eq!(
+			"Step 1 must be finished before step A can begin.".parse::<Day07>(),
+			Err(ParseError::Invalid {
+				line: 1,
+				column: 6,
+				text: "1".to_owned(),
+				expected: "a step",
+			})
+		);
+
+		assert_eq!(
+			"Step A must be finished before step B can begin.\n\
+			 Step C must be finished before step B can begin.\n\
+			 Step B must be finished before step A can begin."
+				.parse::<Day07>(),
+			Err(ParseError::Invalid {
+				line: 1,
+				column: 6,
+				text: "A".to_owned(),
+				expected: "a step without circular dependencies",
+			})
+		);
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(
+			// A step only ever depends on an earlier step, so there are no cycles
+			pairs in vec(
+				(0..25_u8).prop_flat_map(|a| (Just(a), (a + 1)..26)),
+				1..40,
+			)
+		) {
+			let text = pairs
+				.into_iter()
+				.map(|(a, b)| {
+					format!(
+						"Step {} must be finished before step {} can begin.",
+						char::from(b'A' + a),
+						char::from(b'A' + b),
+					)
+				})
+				.collect::<Vec<_>>()
+				.join("\n");
+			let day: Day07 = text.parse().unwrap();
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
@@ -0,0 +1,90 @@
+//! Random license trees with known answers
+
+use common::generate::{Generated, Rng};
+
+// Writes node `i` and its descendants to `nums`, returning the metadata sum
+// and the value of the node
+fn write(
+	rng: &mut Rng,
+	children: &[Vec<usize>],
+	i: usize,
+	nums: &mut Vec<usize>,
+) -> (usize, usize) {
+	let metadata = 1 + rng.below(3);
+
+	nums.push(children[i].len());
+	nums.push(metadata);
+
+	let answers = children[i]
+		.iter()
+		.map(|&child| write(rng, children, child, nums))
+		.collect::<Vec<_>>();
+
+	// Entries of a node with children may be zero or point past the last one
+	let entries = (0..metadata)
+		.map(|_| rng.range(0, answers.len() as i64 + 3) as usize)
+		.collect::<Vec<_>>();
+
+	let sum = entries.iter().sum::<usize>()
+		+ answers.iter().map(|(sum, _)| sum).sum::<usize>();
+
+	let value = if answers.is_empty() {
+		entries.iter().sum()
+	} else {
+		entries
+			.iter()
+			.filter_map(|&entry| answers.get(entry.checked_sub(1)?))
+			.map(|(_, value)| value)
+			.sum()
+	};
+
+	nums.extend(entries);
+
+	(sum, value)
+}
+
+/// Generates a tree of `n` (at least 1) nodes, each attached to a random
+/// earlier node.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<usize, usize> {
+	let n = n.max(1);
+	let mut children = vec![Vec::new(); n];
+
+	for i in 1..n {
+		children[rng.below(i)].push(i);
+	}
+
+	let mut nums = Vec::new();
+	let (sum, value) = write(rng, &children, 0, &mut nums);
+
+	let input = nums
+		.iter()
+		.map(|n| n.to_string())
+		.collect::<Vec<_>>()
+		.join(" ");
+
+	Generated {
+		input,
+		part1: Some(sum),
+		part2: Some(value),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day08;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(8);
+
+		for n in &[1, 2, 10, 1000] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day08 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+			assert_eq!(day.part2().ok(), generated.part2);
+		}
+	}
+}
@@ -0,0 +1,387 @@
+//! Day 8: Memory Maneuver
+
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, ParseError};
+use common::Solution;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Most levels of nodes in a tree. Parsing and solving don't recurse, but
+// printing, comparing and dropping a tree do, once per level.
+const MAX_DEPTH: usize = 1000;
+
+/// A node in the license tree.
+#[derive(Debug, PartialEq)]
+pub struct Node {
+	pub metadata: Vec<usize>,
+	pub children: Vec<Node>,
+}
+
+/// Writes the node as its header, children and then metadata entries, all
+/// separated by spaces.
+impl fmt::Display for Node {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {}", self.children.len(), self.metadata.len())?;
+
+		for child in &self.children {
+			write!(f, " {}", child)?;
+		}
+
+		for entry in &self.metadata {
+			write!(f, " {}", entry)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// The license tree.
+#[derive(Debug, PartialEq)]
+pub struct Day08 {
+	root: Node,
+}
+
+// A number in the license along with the line it was found on
+struct Number<'a> {
+	line: usize,
+	source: &'a str,
+	text: &'a str,
+	value: Result<usize, ParseError>,
+}
+
+// Every number in `input`, in order
+fn numbers(input: &str) -> impl Iterator<Item = Number<'_>> {
+	parse::lines(input).flat_map(|(number, line)| {
+		line.split_whitespace().map(move |text| Number {
+			line: number,
+			source: line,
+			text,
+			value: parse::token(line, text, "a number")
+				.map_err(|err| err.on_line(number)),
+		})
+	})
+}
+
+// Reads the next number, failing at the end of the last line if there is none
+fn next<'a>(
+	nums: &mut impl Iterator<Item = Number<'a>>,
+	end: &ParseError,
+) -> Result<usize, ParseError> {
+	nums.next()
+		.map(|n| n.value)
+		.unwrap_or_else(|| Err(end.clone()))
+}
+
+// A node whose header has been read, with the children parsed so far
+struct Partial {
+	children: Vec<Node>,
+	num_children: usize,
+	num_metadata: usize,
+}
+
+// Parses the root node and everything below it, keeping the nodes still
+// being parsed on a stack rather than recursing, and failing on any node
+// below `MAX_DEPTH`
+fn parse_tree<'a>(
+	nums: &mut impl Iterator<Item = Number<'a>>,
+	end: &ParseError,
+) -> Result<Node, ParseError> {
+	let mut stack: Vec<Partial> = Vec::new();
+
+	loop {
+		let header = nums.next().ok_or_else(|| end.clone())?;
+
+		if stack.len() == MAX_DEPTH {
+			let Number {
+				line, source, text, ..
+			} = header;
+			let expected = "a node nested less deeply";
+
+			return Err(
+				ParseError::invalid(source, text, expected).on_line(line)
+			);
+		}
+
+		stack.push(Partial {
+			children: Vec::new(),
+			num_children: header.value?,
+			num_metadata: next(nums, end)?,
+		});
+
+		// Finish every node that has all of its children
+		while let Some(partial) = stack.last() {
+			if partial.children.len() < partial.num_children {
+				break;
+			}
+
+			let Partial {
+				children,
+				num_metadata,
+				..
+			} = stack.pop().expect("Expected a node being parsed");
+			let metadata = (0..num_metadata)
+				.map(|_| next(nums, end))
+				.collect::<Result<_, _>>()?;
+			let node = Node { metadata, children };
+
+			match stack.last_mut() {
+				Some(parent) => parent.children.push(node),
+				None => return Ok(node),
+			}
+		}
+	}
+}
+
+// Adds every one of `values` to `sum`, or `None` if that overflows
+fn checked_sum<'a>(
+	sum: usize,
+	values: impl IntoIterator<Item = &'a usize>,
+) -> Option<usize> {
+	values
+		.into_iter()
+		.try_fold(sum, |sum, &value| sum.checked_add(value))
+}
+
+impl Day08 {
+	/// Creates a license from its root node.
+	pub fn new(root: Node) -> Self {
+		Self { root }
+	}
+
+	/// Root node of the tree.
+	pub fn root(&self) -> &Node {
+		&self.root
+	}
+
+	/// Sum of every metadata entry in the tree (part 1), or `None` if it's
+	/// too large for a `usize`.
+	pub fn checksum(&self) -> Option<usize> {
+		let mut stack = vec![&self.root];
+		let mut sum = 0;
+
+		while let Some(node) = stack.pop() {
+			sum = checked_sum(sum, &node.metadata)?;
+			stack.extend(&node.children);
+		}
+
+		Some(sum)
+	}
+
+	/// Value of the root node (part 2). Nodes without children are worth
+	/// the sum of their metadata; other nodes are worth the sum of the
+	/// children referenced by their metadata. `None` if any node is worth
+	/// too much for a `usize`.
+	///
+	/// Each node is valued once, after its children, however many times
+	/// it's referenced.
+	pub fn root_value(&self) -> Option<usize> {
+		// Nodes being valued, each with the values of its children so far
+		let mut stack = vec![(&self.root, Vec::new())];
+
+		loop {
+			let (node, values) = stack.last().expect("Expected a node");
+
+			if let Some(child) = node.children.get(values.len()) {
+				stack.push((child, Vec::new()));
+				continue;
+			}
+
+			let value = if node.children.is_empty() {
+				checked_sum(0, &node.metadata)?
+			} else {
+				let referenced = node
+					.metadata
+					.iter()
+					.filter_map(|&n| values.get(n.checked_sub(1)?));
+
+				checked_sum(0, referenced)?
+			};
+
+			stack.pop();
+
+			match stack.last_mut() {
+				Some((_, values)) => values.push(value),
+				None => return Some(value),
+			}
+		}
+	}
+}
+
+impl fmt::Display for Day08 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.root.fmt(f)
+	}
+}
+
+impl FromStr for Day08 {
+	type Err = ParseError;
+
+	/// Parses a license from a whitespace separated list of numbers, failing
+	/// on any node nested more than `MAX_DEPTH` levels deep.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let (number, last) = parse::lines(s).last().ok_or(ParseError::Empty)?;
+		let end =
+			ParseError::missing(last.trim_end(), "a number").on_line(number);
+		let mut nums = numbers(s);
+		let root = parse_tree(&mut nums, &end)?;
+
+		if let Some(Number {
+			line, source, text, ..
+		}) = nums.next()
+		{
+			return Err(
+				ParseError::invalid(source, text, "end of input").on_line(line)
+			);
+		}
+
+		Ok(Self::new(root))
+	}
+}
+
+impl Solution for Day08 {
+	const DAY: u32 = 8;
+
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<usize> {
+		self.checksum()
+			.ok_or_else(|| "checksum too large to hold".into())
+	}
+
+	fn part2(&self) -> common::Result<usize> {
+		self.root_value()
+			.ok_or_else(|| "root value too large to hold".into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
+	static TEST_INPUT: &str = "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2";
+
+	#[test]
+	fn checksum() {
+		let day: Day08 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.checksum(), Some(138));
+	}
+
+	#[test]
+	fn root_value() {
+		let day: Day08 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.root_value(), Some(66));
+
+		// Entries of zero don't refer to any child
+		let day: Day08 = "1 2 0 1 5 0 1".parse().unwrap();
+
+		assert_eq!(day.root_value(), Some(5));
+	}
+
+	#[test]
+	fn overflow() {
+		let day: Day08 = "0 2 18446744073709551615 1".parse().unwrap();
+
+		assert_eq!(day.checksum(), None);
+		assert_eq!(day.root_value(), None);
+		assert!(day.part1().is_err());
+
+		// Every level doubles the value of the one below it
+		let day: Day08 = ("1 2 ".repeat(64) + "0 1 1" + &" 1 1".repeat(64))
+			.parse()
+			.unwrap();
+
+		assert_eq!(day.checksum(), Some(129));
+		assert_eq!(day.root_value(), None);
+
+		let day: Day08 = ("1 2 ".repeat(63) + "0 1 1" + &" 1 1".repeat(63))
+			.parse()
+			.unwrap();
+
+		assert_eq!(day.root_value(), Some(1 << 63));
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"1 1\n0 1 x 1".parse::<Day08>(),
+			Err(ParseError::Invalid {
+				line: 2,
+				column: 5,
+				text: "x".to_owned(),
+				expected: "a number",
+			})
+		);
+
+		assert_eq!(
+			"1 1\n0 1 1".parse::<Day08>(),
+			Err(ParseError::Missing {
+				line: 2,
+				column: 6,
+				expected: "a number",
+			})
+		);
+
+		assert_eq!(
+			"0 1 1 2".parse::<Day08>(),
+			Err(ParseError::Invalid {
+				line: 1,
+				column: 7,
+				text: "2".to_owned(),
+				expected: "end of input",
+			})
+		);
+
+		let deep = "1 0 ".repeat(200_000) + "0 0";
+
+		assert_eq!(
+			deep.parse::<Day08>(),
+			Err(ParseError::Invalid {
+				line: 1,
+				column: 4001,
+				text: "1".to_owned(),
+				expected: "a node nested less deeply",
+			})
+		);
+
+		let deepest = "1 1 ".repeat(MAX_DEPTH - 1)
+			+ "0 1 7" + &" 1".repeat(MAX_DEPTH - 1);
+		let day: Day08 = deepest.parse().unwrap();
+
+		assert_eq!(day.checksum(), Some(7 + MAX_DEPTH - 1));
+		assert_eq!(day.root_value(), Some(7));
+		assert_eq!(day.to_string().parse(), Ok(day));
+	}
+
+	fn node() -> impl Strategy<Value = Node> {
+		let leaf = vec(any::<usize>(), 0..4).prop_map(|metadata| Node {
+			metadata,
+			children: Vec::new(),
+		});
+
+		leaf.prop_recursive(6, 64, 4, |node| {
+			(vec(node, 0..4), vec(any::<usize>(), 0..4))
+				.prop_map(|(children, metadata)| Node { metadata, children })
+		})
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(root in node()) {
+			let day = Day08::new(root);
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
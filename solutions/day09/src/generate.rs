@@ -0,0 +1,62 @@
+//! Random marble games with known answers
+
+use std::collections::VecDeque;
+
+use common::generate::{Generated, Rng};
+
+// Plays the game with the current marble kept at the back of the circle
+fn high_score(num_players: usize, num_marbles: usize) -> usize {
+	let mut circle = VecDeque::with_capacity(num_marbles + 1);
+	let mut scores = vec![0; num_players];
+
+	circle.push_back(0);
+
+	for marble in 1..=num_marbles {
+		if marble.is_multiple_of(23) {
+			circle.rotate_right(7);
+			scores[(marble - 1) % num_players] +=
+				marble + circle.pop_back().unwrap();
+			circle.rotate_left(1);
+		} else {
+			circle.rotate_left(1);
+			circle.push_back(marble);
+		}
+	}
+
+	scores.into_iter().max().unwrap_or(0)
+}
+
+/// Generates a game between 2 and 500 players whose last marble is worth
+/// `n` points.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<usize, usize> {
+	let num_players = rng.range(2, 501) as usize;
+
+	Generated {
+		input: format!(
+			"{} players; last marble is worth {} points",
+			num_players, n
+		),
+		part1: Some(high_score(num_players, n)),
+		part2: Some(high_score(num_players, n * 100)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day09;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(9);
+
+		for n in &[0, 1, 25, 1000] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day09 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+			assert_eq!(day.part2().ok(), generated.part2);
+		}
+	}
+}
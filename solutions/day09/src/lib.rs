@@ -0,0 +1,290 @@
+//! Day 9: Marble Mania
+
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Most marbles worth playing, keeping the hundredfold game of part 2 to a
+// couple of hundred megabytes
+const MAX_MARBLES: usize = 1 << 17;
+
+/// A marble's neighbours in the circle, identified by marble value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Marble {
+	/// Marble counter-clockwise of this one
+	pub prev: usize,
+	/// Marble clockwise of this one
+	pub next: usize,
+}
+
+impl Marble {
+	fn new() -> Self {
+		Self { prev: 0, next: 0 }
+	}
+}
+
+/// A completed marble game.
+#[derive(Debug, PartialEq)]
+pub struct Day09 {
+	pub num_players: usize,
+	pub num_marbles: usize,
+	next_marble: usize,
+	next_player: usize,
+	position: usize,
+	game: Vec<Marble>,
+	scores: Vec<usize>,
+}
+
+impl Day09 {
+	/// Plays a game with `num_players` until the marble worth `num_marbles`
+	/// has been placed. Parsing rejects games with more than `MAX_MARBLES`
+	/// marbles.
+	pub fn new(num_players: usize, num_marbles: usize) -> Self {
+		let day = Day09 {
+			num_players,
+			num_marbles,
+			next_marble: 1,
+			next_player: 0,
+			position: 0,
+			game: vec![Marble::new(); num_marbles + 1],
+			scores: vec![0; num_players],
+		};
+
+		day.play()
+	}
+
+	fn insert_after(&mut self, prev: usize, n: usize) {
+		let next = self.game[prev].next;
+
+		self.game[n].prev = prev;
+		self.game[n].next = next;
+		self.game[prev].next = n;
+		self.game[next].prev = n;
+	}
+
+	fn remove(&mut self, i: usize) {
+		let prev = self.game[i].prev;
+		let next = self.game[i].next;
+
+		self.game[prev].next = next;
+		self.game[next].prev = prev;
+	}
+
+	fn normal_move(&mut self) {
+		self.insert_after(self.game[self.position].next, self.next_marble);
+		self.position = self.next_marble;
+	}
+
+	fn scoring_move(&mut self) {
+		let node = (0..7).fold(self.position, |i, _| self.game[i].prev);
+
+		self.scores[self.next_player] += self.next_marble + node;
+		self.position = self.game[node].next;
+		self.remove(node);
+	}
+
+	fn next_move(&mut self) {
+		if self.next_marble.is_multiple_of(23) {
+			self.scoring_move();
+		} else {
+			self.normal_move();
+		}
+
+		self.next_marble += 1;
+		self.next_player = (self.next_player + 1) % self.num_players;
+	}
+
+	fn play(mut self) -> Self {
+		while self.next_marble <= self.num_marbles {
+			self.next_move();
+		}
+
+		self
+	}
+
+	/// The circle as a doubly linked list indexed by marble value. Marbles
+	/// removed while scoring keep their last neighbours.
+	pub fn circle(&self) -> &[Marble] {
+		&self.game
+	}
+
+	/// Score of each player.
+	pub fn scores(&self) -> &[usize] {
+		&self.scores
+	}
+
+	/// Winning elf's score (part 1).
+	pub fn max_score(&self) -> usize {
+		*self.scores.iter().max().unwrap_or(&0)
+	}
+
+	/// The same game played with a hundred times as many marbles (part 2),
+	/// if that many marbles can be counted.
+	pub fn big_game(&self) -> Option<Self> {
+		// A multiple of 100 is never `usize::MAX`, so the circle's extra
+		// slot for marble 0 always fits as well
+		let num_marbles = self.num_marbles.checked_mul(100)?;
+
+		Some(Self::new(self.num_players, num_marbles))
+	}
+}
+
+impl fmt::Display for Day09 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} players; last marble is worth {} points",
+			self.num_players, self.num_marbles
+		)
+	}
+}
+
+impl FromStr for Day09 {
+	type Err = ParseError;
+
+	/// Parses `9 players; last marble is worth 25 points`, failing if the
+	/// last marble is worth more than `MAX_MARBLES` points.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		parse::single_line(s, |line| {
+			let mut cursor = Cursor::new(line);
+
+			let players = cursor
+				.skip_whitespace()
+				.take_while(|c| c.is_ascii_digit(), "a number of players")?;
+			let num_players =
+				parse::token(line, players, "a number of players")?;
+			cursor.skip_whitespace().literal("players;", "`players;`")?;
+			cursor
+				.skip_whitespace()
+				.literal("last marble is worth", "`last marble is worth`")?;
+			let value = cursor.skip_whitespace().rest();
+			let num_marbles = cursor.integer::<usize>("a marble value")?;
+			let value = &value[..(value.len() - cursor.rest().len())];
+			cursor.skip_whitespace().literal("points", "`points`")?;
+			cursor.end()?;
+
+			if num_players == 0 {
+				return Err(ParseError::invalid(
+					line,
+					players,
+					"at least one player",
+				));
+			}
+
+			if num_marbles > MAX_MARBLES {
+				return Err(ParseError::invalid(
+					line,
+					value,
+					"a smaller marble value",
+				));
+			}
+
+			Ok(Self::new(num_players, num_marbles))
+		})
+	}
+}
+
+impl Solution for Day09 {
+	const DAY: u32 = 9;
+
+	type Part1 = usize;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<usize> {
+		Ok(self.max_score())
+	}
+
+	fn part2(&self) -> common::Result<usize> {
+		self.big_game()
+			.map(|day| day.max_score())
+			.ok_or_else(|| "too many marbles to play".into())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	#[test]
+	fn parse() {
+		let day: Day09 =
+			"9 players; last marble is worth 25 points".parse().unwrap();
+
+		assert_eq!(day.num_players, 9);
+		assert_eq!(day.num_marbles, 25);
+	}
+
+	#[test]
+	fn max_score() {
+		assert_eq!(Day09::new(9, 25).max_score(), 32);
+		assert_eq!(Day09::new(10, 1618).max_score(), 8317);
+		assert_eq!(Day09::new(13, 7999).max_score(), 146373);
+		assert_eq!(Day09::new(17, 1104).max_score(), 2764);
+		assert_eq!(Day09::new(21, 6111).max_score(), 54718);
+		assert_eq!(Day09::new(30, 5807).max_score(), 37305);
+	}
+
+	#[test]
+	fn big_game() {
+		let day = Day09::new(9, 25).big_game().unwrap();
+
+		assert_eq!(day.num_players, 9);
+		assert_eq!(day.num_marbles, 2500);
+		assert_eq!(day.max_score(), Day09::new(9, 2500).max_score());
+
+		let day = Day09::new(9, 0);
+		let day = Day09 {
+			num_marbles: usize::MAX / 10,
+			..day
+		};
+
+		assert!(day.big_game().is_none());
+		assert!(day.part2().is_err());
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"9 players; last marble is worth lots points"
+				.parse::<Day09>()
+				.unwrap_err(),
+			ParseError::Invalid {
+				line: 1,
+				column: 33,
+				text: "lots".to_owned(),
+				expected: "a marble value",
+			}
+		);
+
+		assert_eq!(
+			"9 players; last marble is worth 18446744073709551615 points"
+				.parse::<Day09>()
+				.unwrap_err(),
+			ParseError::Invalid {
+				line: 1,
+				column: 33,
+				text: "18446744073709551615".to_owned(),
+				expected: "a smaller marble value",
+			}
+		);
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(num_players in 1..500_usize, num_marbles in 0..5000_usize) {
+			let day = Day09::new(num_players, num_marbles);
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
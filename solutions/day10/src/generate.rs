@@ -0,0 +1,101 @@
+//! Random skies with known answers
+
+use std::collections::HashSet;
+
+use common::generate::{Generated, Rng};
+
+// Rows the message spans
+const HEIGHT: i64 = 10;
+
+// Empty space around the rendered message
+const BORDER: i64 = 4;
+
+fn render(targets: &[(i64, i64)], width: i64) -> String {
+	let lit = targets.iter().collect::<HashSet<_>>();
+
+	(-BORDER..(HEIGHT + BORDER))
+		.map(|y| {
+			(-BORDER..(width + BORDER))
+				.map(|x| if lit.contains(&(x, y)) { '#' } else { '.' })
+				.collect::<String>()
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+fn speed(rng: &mut Rng) -> i64 {
+	rng.range(1, 6)
+}
+
+/// Generates `n` (at least 4) points that spell out a message 10 rows tall
+/// after several thousand seconds. The first and last columns of the
+/// message each hold a point moving left and a point moving right, so the
+/// message is narrower than the sky at any other time. The points in the
+/// first and last columns also sit on the first and last rows.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<String, i64> {
+	let n = n.max(4);
+	let width = (n as i64 / 4).max(2);
+	let time = rng.range(5000, 15000);
+
+	let points = (0..n)
+		.map(|i| {
+			let y = match i {
+				0 => 0,
+				2 => HEIGHT - 1,
+				_ => rng.range(0, HEIGHT),
+			};
+			let (x, dx) = match i {
+				0 => (0, -speed(rng)),
+				1 => (0, speed(rng)),
+				2 => (width - 1, -speed(rng)),
+				3 => (width - 1, speed(rng)),
+				_ if rng.one_in(2) => (rng.range(0, width), -speed(rng)),
+				_ => (rng.range(0, width), speed(rng)),
+			};
+
+			((x, y), (dx, rng.range(-5, 6)))
+		})
+		.collect::<Vec<_>>();
+
+	let targets = points.iter().map(|&(xy, _)| xy).collect::<Vec<_>>();
+	let mut lines = points
+		.iter()
+		.map(|&((x, y), (dx, dy))| {
+			format!(
+				"position=<{:>6}, {:>6}> velocity=<{:>2}, {:>2}>",
+				x - time * dx,
+				y - time * dy,
+				dx,
+				dy
+			)
+		})
+		.collect::<Vec<_>>();
+
+	rng.shuffle(&mut lines);
+
+	Generated {
+		input: lines.join("\n"),
+		part1: Some(render(&targets, width)),
+		part2: Some(time),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day10;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(10);
+
+		for n in &[4, 10, 100] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day10 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+			assert_eq!(day.part2().ok(), generated.part2);
+		}
+	}
+}
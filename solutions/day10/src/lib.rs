@@ -0,0 +1,432 @@
+//! Day 10: The Stars Align
+
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+use grid::Grid;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Most cells a drawing of the sky may take up, border included
+const MAX_AREA: i128 = 1 << 24;
+
+// Empty cells drawn around the points
+const BORDER: i128 = 4;
+
+#[derive(Debug)]
+struct XY(i64, i64);
+
+impl XY {
+	// <-6, 10>
+	fn parse(cursor: &mut Cursor) -> Result<Self, ParseError> {
+		cursor.skip_whitespace().literal("<", "`<`")?;
+		let x = cursor.skip_whitespace().integer("an X value")?;
+		cursor.skip_whitespace().literal(",", "`,`")?;
+		let y = cursor.skip_whitespace().integer("a Y value")?;
+		cursor.skip_whitespace().literal(">", "`>`")?;
+
+		Ok(Self(x, y))
+	}
+}
+
+/// Location of a point in the sky.
+#[derive(Debug, PartialEq)]
+pub struct Position {
+	pub x: i64,
+	pub y: i64,
+}
+
+/// Distance a point moves each second.
+#[derive(Debug, PartialEq)]
+pub struct Velocity {
+	pub x: i64,
+	pub y: i64,
+}
+
+/// A moving point of light, e.g.
+/// `position=< 9,  1> velocity=< 0,  2>`.
+#[derive(Debug, PartialEq)]
+pub struct Point {
+	pub position: Position,
+	pub velocity: Velocity,
+}
+
+impl Point {
+	/// Position of the point after `time` seconds, or `None` if it's out of
+	/// range.
+	pub fn advance(&self, time: i64) -> Option<Position> {
+		let advance = |position: i64, velocity: i64| {
+			time.checked_mul(velocity)?.checked_add(position)
+		};
+
+		Some(Position {
+			x: advance(self.position.x, self.velocity.x)?,
+			y: advance(self.position.y, self.velocity.y)?,
+		})
+	}
+}
+
+impl FromStr for Point {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let mut cursor = Cursor::new(s);
+
+		cursor
+			.skip_whitespace()
+			.literal("position=", "`position=`")?;
+		let position = XY::parse(&mut cursor)?;
+		cursor
+			.skip_whitespace()
+			.literal("velocity=", "`velocity=`")?;
+		let velocity = XY::parse(&mut cursor)?;
+		cursor.end()?;
+
+		Ok(Self {
+			position: Position {
+				x: position.0,
+				y: position.1,
+			},
+			velocity: Velocity {
+				x: velocity.0,
+				y: velocity.1,
+			},
+		})
+	}
+}
+
+impl fmt::Display for Point {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let Point { position, velocity } = self;
+
+		write!(
+			f,
+			"position=<{:>2}, {:>2}> velocity=<{:>2}, {:>2}>",
+			position.x, position.y, velocity.x, velocity.y
+		)
+	}
+}
+
+// Top-left position and size of a drawing of `points`, if it's small enough
+// to draw
+fn canvas(points: &[Position]) -> Option<(i128, i128, usize, usize)> {
+	let xs = points.iter().map(|p| i128::from(p.x));
+	let ys = points.iter().map(|p| i128::from(p.y));
+	let (min_x, max_x) = (xs.clone().min()?, xs.max()?);
+	let (min_y, max_y) = (ys.clone().min()?, ys.max()?);
+	let width = max_x - min_x + 2 * BORDER + 1;
+	let height = max_y - min_y + 2 * BORDER + 1;
+
+	if width.checked_mul(height)? > MAX_AREA {
+		return None;
+	}
+
+	Some((min_x, min_y, width as usize, height as usize))
+}
+
+/// Every point of light in the sky.
+#[derive(Debug)]
+pub struct Day10 {
+	points: Vec<Point>,
+}
+
+impl Day10 {
+	/// Creates a sky from its points of light.
+	pub fn new(points: Vec<Point>) -> Self {
+		Self { points }
+	}
+
+	/// Position of every point after `time` seconds, or `None` if any point
+	/// is out of range.
+	pub fn advance(&self, time: i64) -> Option<Vec<Position>> {
+		self.points
+			.iter()
+			.map(|point| point.advance(time))
+			.collect()
+	}
+
+	/// Renders the sky after `time` seconds with `#` for points and `.` for
+	/// empty space (part 1), or `None` if a point is out of range or the
+	/// points are too spread out to draw.
+	pub fn graph(&self, time: i64) -> Option<String> {
+		let points = self.advance(time)?;
+		let (min_x, min_y, width, height) = canvas(&points)?;
+		let mut grid = Grid::new(width, height, '.');
+
+		for p in &points {
+			let x = i128::from(p.x) - min_x + BORDER;
+			let y = i128::from(p.y) - min_y + BORDER;
+
+			grid[(x as usize, y as usize)] = '#';
+		}
+
+		Some(grid.render(|&c| c))
+	}
+
+	// Index of a point keeping the sky from being drawn after `time`
+	// seconds: the first out of range, or else the furthest from the first
+	fn stray(&self, time: i64) -> usize {
+		let points = self
+			.points
+			.iter()
+			.map(|point| point.advance(time))
+			.collect::<Vec<_>>();
+
+		if let Some(i) = points.iter().position(Option::is_none) {
+			return i;
+		}
+
+		let points = points.into_iter().flatten().collect::<Vec<_>>();
+		let distance = |p: &Position| {
+			let (x, y) = (i128::from(p.x), i128::from(p.y));
+
+			(x - i128::from(points[0].x)).abs()
+				+ (y - i128::from(points[0].y)).abs()
+		};
+
+		(0..points.len())
+			.max_by_key(|&i| distance(&points[i]))
+			.unwrap_or(0)
+	}
+
+	// Horizontal spread of the points after `time` seconds, widened so it
+	// can't overflow
+	fn width(&self, time: i64) -> i128 {
+		let xs = self.points.iter().map(|p| {
+			i128::from(p.position.x)
+				+ i128::from(time) * i128::from(p.velocity.x)
+		});
+
+		let min_x = xs.clone().min().unwrap_or(0);
+		let max_x = xs.max().unwrap_or(0);
+
+		max_x - min_x
+	}
+
+	/// Seconds until the message appears, taken as the first moment the
+	/// points are closest together horizontally (part 2).
+	pub fn guess(&self) -> i64 {
+		let mut velocities = self.points.iter().map(|p| p.velocity.x);
+		let first = velocities.next();
+
+		// The width never changes, so the message is already there
+		if velocities.all(|x| Some(x) == first) {
+			return 0;
+		}
+
+		// The width shrinks and then grows, so binary search for the first
+		// second after which it grows
+		let (mut lo, mut hi) = (0, i64::MAX);
+
+		while lo < hi {
+			let mid = lo + (hi - lo) / 2;
+
+			if self.width(mid + 1) > self.width(mid) {
+				hi = mid;
+			} else {
+				lo = mid + 1;
+			}
+		}
+
+		lo
+	}
+}
+
+impl FromStr for Day10 {
+	type Err = ParseError;
+
+	/// Parses one point per line, failing if the points are too spread out
+	/// to draw when the message appears.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let day = Self::new(parse::parse_lines(s, str::parse)?);
+		let time = day.guess();
+
+		if day.advance(time).as_deref().and_then(canvas).is_none() {
+			let (number, line) = parse::lines(s).nth(day.stray(time)).unwrap();
+			let expected = "a point that lines up with the others";
+
+			return Err(ParseError::invalid(line, line.trim(), expected)
+				.on_line(number));
+		}
+
+		Ok(day)
+	}
+}
+
+impl Solution for Day10 {
+	const DAY: u32 = 10;
+
+	type Part1 = String;
+	type Part2 = i64;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<String> {
+		self.graph(self.guess())
+			.ok_or_else(|| "points too spread out to draw".into())
+	}
+
+	fn part2(&self) -> common::Result<i64> {
+		Ok(self.guess())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	static TEST_INPUT: &str = r#"
+        position=< 9,  1> velocity=< 0,  2>
+        position=< 7,  0> velocity=<-1,  0>
+        position=< 3, -2> velocity=<-1,  1>
+        position=< 6, 10> velocity=<-2, -1>
+        position=< 2, -4> velocity=< 2,  2>
+        position=<-6, 10> velocity=< 2, -2>
+        position=< 1,  8> velocity=< 1, -1>
+        position=< 1,  7> velocity=< 1,  0>
+        position=<-3, 11> velocity=< 1, -2>
+        position=< 7,  6> velocity=<-1, -1>
+        position=<-2,  3> velocity=< 1,  0>
+        position=<-4,  3> velocity=< 2,  0>
+        position=<10, -3> velocity=<-1,  1>
+        position=< 5, 11> velocity=< 1, -2>
+        position=< 4,  7> velocity=< 0, -1>
+        position=< 8, -2> velocity=< 0,  1>
+        position=<15,  0> velocity=<-2,  0>
+        position=< 1,  6> velocity=< 1,  0>
+        position=< 8,  9> velocity=< 0, -1>
+        position=< 3,  3> velocity=<-1,  1>
+        position=< 0,  5> velocity=< 0, -1>
+        position=<-2,  2> velocity=< 2,  0>
+        position=< 5, -2> velocity=< 1,  2>
+        position=< 1,  4> velocity=< 2,  1>
+        position=<-2,  7> velocity=< 2, -2>
+        position=< 3,  6> velocity=<-1, -1>
+        position=< 5,  0> velocity=< 1,  0>
+        position=<-6,  0> velocity=< 2,  0>
+        position=< 5,  9> velocity=< 1, -2>
+        position=<14,  7> velocity=<-2,  0>
+        position=<-3,  6> velocity=< 2, -1>
+    "#;
+
+	#[test]
+	fn guess() {
+		let day: Day10 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.guess(), 3);
+
+		// Points moving together never get any closer
+		let day: Day10 = "position=< 0, 0> velocity=< 1, 0>\n\
+		                  position=< 5, 0> velocity=< 1, 1>"
+			.parse()
+			.unwrap();
+
+		assert_eq!(day.guess(), 0);
+
+		// Points already lined up vertically start out closest
+		let day: Day10 = "position=< 0, 0> velocity=< 1, 0>\n\
+		                  position=< 0, 5> velocity=<-1, 0>"
+			.parse()
+			.unwrap();
+
+		assert_eq!(day.guess(), 0);
+	}
+
+	#[test]
+	fn graph() {
+		let day: Day10 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(
+			day.graph(3).unwrap(),
+			r#"
+..................
+..................
+..................
+..................
+....#...#..###....
+....#...#...#.....
+....#...#...#.....
+....#####...#.....
+....#...#...#.....
+....#...#...#.....
+....#...#...#.....
+....#...#..###....
+..................
+..................
+..................
+..................
+        "#
+			.trim()
+		);
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"position=< 9,  1> velocity=< 0;  2>"
+				.parse::<Day10>()
+				.unwrap_err(),
+			ParseError::Invalid {
+				line: 1,
+				column: 31,
+				text: ";".to_owned(),
+				expected: "`,`",
+			}
+		);
+
+		assert_eq!(
+			"position=<0, 0> velocity=<1, 0>\n\
+			 position=<100000000, 100000000> velocity=<1, 0>"
+				.parse::<Day10>()
+				.unwrap_err(),
+			ParseError::Invalid {
+				line: 2,
+				column: 1,
+				text: "position=<100000000, 100000000> velocity=<1, 0>"
+					.to_owned(),
+				expected: "a point that lines up with the others",
+			}
+		);
+	}
+
+	#[test]
+	fn out_of_range() {
+		let point = Point {
+			position: Position { x: i64::MAX, y: 0 },
+			velocity: Velocity { x: 1, y: 0 },
+		};
+
+		assert_eq!(point.advance(0), Some(Position { x: i64::MAX, y: 0 }));
+		assert_eq!(point.advance(1), None);
+
+		let day = Day10::new(vec![point]);
+
+		assert_eq!(day.graph(1), None);
+		assert_eq!(
+			day.graph(0),
+			Some(
+				".........\n".repeat(4)
+					+ "....#....\n" + &".........\n".repeat(3)
+					+ "........."
+			)
+		);
+	}
+
+	proptest! {
+		#[test]
+		fn point_round_trip((x, y, dx, dy) in any::<(i64, i64, i64, i64)>()) {
+			let point = Point {
+				position: Position { x, y },
+				velocity: Velocity { x: dx, y: dy },
+			};
+
+			prop_assert_eq!(point.to_string().parse(), Ok(point));
+		}
+	}
+}
@@ -0,0 +1,110 @@
+//! Random grid serial numbers with known answers
+
+use common::generate::{Generated, Rng};
+
+use crate::Day11;
+
+const SIZE: usize = 300;
+
+// Sums of the power levels above and to the left of each cell, so that any
+// square can be totalled from its four corners
+struct Sums(Vec<Vec<i64>>);
+
+impl Sums {
+	fn new(serial: i64) -> Self {
+		let mut sums = vec![vec![0; SIZE + 1]; SIZE + 1];
+
+		for x in 1..=SIZE {
+			for y in 1..=SIZE {
+				sums[x][y] = Day11::power_level(serial, x as i64, y as i64)
+					+ sums[x - 1][y]
+					+ sums[x][y - 1]
+					- sums[x - 1][y - 1];
+			}
+		}
+
+		Sums(sums)
+	}
+
+	// Total power of the `n`x`n` square with its top-left cell at `(x, y)`
+	fn square(&self, n: usize, x: usize, y: usize) -> i64 {
+		let (x0, y0, x1, y1) = (x - 1, y - 1, x + n - 1, y + n - 1);
+
+		self.0[x1][y1] - self.0[x0][y1] - self.0[x1][y0] + self.0[x0][y0]
+	}
+
+	// Square with the largest total power among `sizes`, preferring the last
+	// one visited like the solver does
+	fn largest(
+		&self,
+		sizes: impl Fn(usize, usize) -> Vec<usize>,
+	) -> (usize, usize, usize) {
+		let mut best = (0, 0, 0, i64::MIN);
+
+		for x in 1..=SIZE {
+			for y in 1..=SIZE {
+				for n in sizes(x, y) {
+					let total = self.square(n, x, y);
+
+					if total >= best.3 {
+						best = (x, y, n, total);
+					}
+				}
+			}
+		}
+
+		(best.0, best.1, best.2)
+	}
+}
+
+/// Generates a random grid serial number. The grid is always 300x300, so
+/// `n` is unused.
+pub fn generate(rng: &mut Rng, _n: usize) -> Generated<String, String> {
+	let serial = rng.range(1, 10_000);
+	let sums = Sums::new(serial);
+	let fits =
+		|x: usize, y: usize, n: usize| x + n <= SIZE + 1 && y + n <= SIZE + 1;
+
+	let (x, y, _) =
+		sums.largest(|x, y| if fits(x, y, 3) { vec![3] } else { vec![] });
+	let part1 = format!("{},{}", x, y);
+
+	let (x, y, n) =
+		sums.largest(|x, y| (1..=SIZE).filter(|&n| fits(x, y, n)).collect());
+	let part2 = format!("{},{},{}", x, y, n);
+
+	Generated {
+		input: serial.to_string(),
+		part1: Some(part1),
+		part2: Some(part2),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(11);
+
+		for _ in 0..2 {
+			let generated = super::generate(&mut rng, 0);
+			let day: Day11 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+		}
+	}
+
+	// Skip test because it requires `--release` to run in a reasonable amount
+	// of time.
+	#[test]
+	#[ignore]
+	fn generate_part2() {
+		let generated = super::generate(&mut Rng::new(11), 0);
+		let day: Day11 = generated.input.parse().unwrap();
+
+		assert_eq!(day.part2().ok(), generated.part2);
+	}
+}
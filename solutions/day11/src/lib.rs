@@ -0,0 +1,251 @@
+//! Day 11: Chronal Charge
+
+use std::cmp;
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+use grid::Grid;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+/// A 300x300 grid of fuel cells addressed with 1-based `(x, y)`
+/// coordinates.
+#[derive(Debug, PartialEq)]
+pub struct Day11 {
+	serial: i64,
+	grid: Grid<i64>,
+}
+
+impl Day11 {
+	/// Power level of the fuel cell at `(x, y)` for a grid serial number.
+	pub fn power_level(serial: i64, x: i64, y: i64) -> i64 {
+		// Find the fuel cell's rack ID, which is its X coordinate plus 10.
+		// Widened so that no serial number can overflow.
+		let rack_id = i128::from(x) + 10;
+
+		// Begin with a power level of the rack ID times the Y coordinate.
+		let mut power_level = rack_id * i128::from(y);
+
+		// Increase the power level by the value of the grid serial number.
+		power_level += i128::from(serial);
+
+		// Set the power level to itself multiplied by the rack ID.
+		power_level *= rack_id;
+
+		// Keep only the hundreds digit of the power level.
+		power_level = (power_level / 100) % 10;
+
+		// Subtract 5 from the power level.
+		power_level -= 5;
+
+		power_level as i64
+	}
+
+	/// Computes the power level of every cell for a grid serial number.
+	pub fn new(serial: i64) -> Self {
+		let grid = Grid::from_fn(300, 300, |x, y| {
+			Self::power_level(serial, x as i64 + 1, y as i64 + 1)
+		});
+
+		Self { serial, grid }
+	}
+
+	/// Grid serial number
+	pub fn serial(&self) -> i64 {
+		self.serial
+	}
+
+	/// Power level of the cell at `(x, y)`.
+	pub fn cell(&self, x: usize, y: usize) -> i64 {
+		self.grid[(x - 1, y - 1)]
+	}
+
+	/// Total power of the `n`x`n` square with its top-left cell at `(x, y)`.
+	/// Returns `None` if the square doesn't fit on the grid.
+	pub fn total_power(&self, n: usize, x: usize, y: usize) -> Option<i64> {
+		let view =
+			self.grid.view(x.checked_sub(1)?, y.checked_sub(1)?, n, n)?;
+
+		Some(view.cells().sum())
+	}
+
+	/// Top-left coordinate and total power of the `size`x`size` square with
+	/// the largest total power (part 1 uses a size of 3). Returns `None` if
+	/// `size` is zero or the square doesn't fit on the grid.
+	pub fn largest_power(&self, size: usize) -> Option<((usize, usize), i64)> {
+		if size == 0 || size > 300 {
+			return None;
+		}
+
+		let n = 300 - size + 2;
+
+		(1..n)
+			.flat_map(|x| (1..n).map(move |y| (x, y)))
+			.filter_map(|(x, y)| Some(((x, y), self.total_power(size, x, y)?)))
+			.max_by_key(|(_, total_power)| *total_power)
+	}
+
+	/// Top-left coordinate and size of the square of any size with the
+	/// largest total power (part 2). Columns of top-left corners are
+	/// searched in parallel with the `parallel` feature.
+	pub fn largest_power_grid(&self) -> ((usize, usize), usize) {
+		let xs = 1..301;
+
+		#[cfg(feature = "parallel")]
+		let xs = xs.into_par_iter();
+
+		// Largest square in each column of top-left corners, then the
+		// largest of those
+		xs.filter_map(|x| {
+			(1..301)
+				.flat_map(move |y| {
+					let max_size = 302 - cmp::max(x, y);
+					let mut total = 0;
+
+					(1..max_size).map(move |n| {
+						let row = (x..(x + n))
+							.fold(0, |acc, x| acc + self.cell(x, y + n - 1));
+
+						let col = (y..(y + n - 1))
+							.fold(0, |acc, y| acc + self.cell(x + n - 1, y));
+
+						total += row + col;
+
+						((x, y), n, total)
+					})
+				})
+				.max_by_key(|(_, _, total_power)| *total_power)
+		})
+		.max_by_key(|(_, _, total_power)| *total_power)
+		.map(|(xy, n, _)| (xy, n))
+		.unwrap_or(((0, 0), 0))
+	}
+}
+
+impl fmt::Display for Day11 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.serial)
+	}
+}
+
+impl FromStr for Day11 {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		parse::single_line(s, |line| {
+			let mut cursor = Cursor::new(line);
+			let serial =
+				cursor.skip_whitespace().integer("a grid serial number")?;
+
+			cursor.end()?;
+
+			Ok(Self::new(serial))
+		})
+	}
+}
+
+impl Solution for Day11 {
+	const DAY: u32 = 11;
+
+	type Part1 = String;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<String> {
+		let ((x, y), _) = self
+			.largest_power(3)
+			.ok_or("a 3x3 square doesn't fit on the grid")?;
+
+		Ok(format!("{},{}", x, y))
+	}
+
+	fn part2(&self) -> common::Result<String> {
+		let ((x, y), n) = self.largest_power_grid();
+
+		Ok(format!("{},{},{}", x, y, n))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	#[test]
+	fn cell() {
+		assert_eq!(Day11::new(8).cell(3, 5), 4);
+		assert_eq!(Day11::new(57).cell(122, 79), -5);
+		assert_eq!(Day11::new(39).cell(217, 196), 0);
+		assert_eq!(Day11::new(71).cell(101, 153), 4);
+		assert_eq!(Day11::power_level(i64::MAX, 1, 1), 4);
+	}
+
+	#[test]
+	fn total_power() {
+		let day = Day11::new(18);
+
+		assert_eq!(day.total_power(3, 33, 45), Some(29));
+		assert!(day.total_power(300, 1, 1).is_some());
+		assert_eq!(day.total_power(3, 0, 45), None);
+		assert_eq!(day.total_power(3, 299, 45), None);
+		assert_eq!(day.total_power(usize::MAX, 1, 1), None);
+	}
+
+	#[test]
+	fn largest_power() {
+		let corner = |serial, size| {
+			Day11::new(serial).largest_power(size).map(|(xy, _)| xy)
+		};
+
+		assert_eq!(corner(18, 3), Some((33, 45)));
+		assert_eq!(corner(42, 3), Some((21, 61)));
+		assert_eq!(corner(18, 16), Some((90, 269)));
+		assert_eq!(corner(42, 12), Some((232, 251)));
+		assert_eq!(corner(18, 300), Some((1, 1)));
+		assert_eq!(corner(18, 0), None);
+		assert_eq!(corner(18, 301), None);
+		assert_eq!(corner(18, usize::MAX), None);
+	}
+
+	// Skip test because it requires `--release` to run in a reasonable amount
+	// of time.
+	#[test]
+	#[ignore]
+	fn largest_power_grid() {
+		assert_eq!(Day11::new(18).largest_power_grid(), ((90, 269), 16));
+		assert_eq!(Day11::new(42).largest_power_grid(), ((232, 251), 12));
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"18 19".parse::<Day11>().unwrap_err(),
+			ParseError::Invalid {
+				line: 1,
+				column: 4,
+				text: "19".to_owned(),
+				expected: "end of line",
+			}
+		);
+	}
+
+	proptest! {
+		// Every case computes a whole grid, so keep the count down
+		#![proptest_config(ProptestConfig::with_cases(32))]
+
+		#[test]
+		fn round_trip(serial in any::<i64>()) {
+			let day = Day11::new(serial);
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
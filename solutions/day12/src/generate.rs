@@ -0,0 +1,65 @@
+//! Random pots with known answers
+
+use common::generate::{Generated, Rng};
+
+/// Generates `n` (at least 1) initial pots and notes that move every plant
+/// one pot left, one pot right or keep it where it is each generation.
+/// Some notes that leave a pot empty are left out, as in the example.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<i64, i64> {
+	let pots = (0..n.max(1))
+		.map(|i| if i == 0 || rng.one_in(2) { '#' } else { '.' })
+		.collect::<String>();
+
+	// Plants grow where the pot `shift` to the left had one
+	let shift = rng.range(-1, 2);
+	let source = (2 - shift) as usize;
+
+	let mut notes = (0..32)
+		.map(|pattern: usize| {
+			let from = (0..5)
+				.map(|i| if pattern & (1 << i) != 0 { '#' } else { '.' })
+				.collect::<String>();
+			let to = from.as_bytes()[source] as char;
+
+			(from, to)
+		})
+		.filter(|(_, to)| *to == '#' || !rng.one_in(3))
+		.map(|(from, to)| format!("{} => {}", from, to))
+		.collect::<Vec<_>>();
+
+	rng.shuffle(&mut notes);
+
+	let plants = pots
+		.char_indices()
+		.filter(|(_, c)| *c == '#')
+		.map(|(i, _)| i as i64);
+	let after = |generations: i64| {
+		plants.clone().map(|i| i + shift * generations).sum::<i64>()
+	};
+
+	Generated {
+		input: format!("initial state: {}\n\n{}", pots, notes.join("\n")),
+		part1: Some(after(20)),
+		part2: Some(after(50_000_000_000)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day12;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(12);
+
+		for n in &[1, 10, 100, 100, 100] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day12 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+			assert_eq!(day.part2().ok(), generated.part2);
+		}
+	}
+}
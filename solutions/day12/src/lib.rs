@@ -0,0 +1,367 @@
+//! Day 12: Subterranean Sustainability
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Most generations worth simulating before giving up on the plants ever
+// repeating a pattern
+const MAX_GENERATIONS: u64 = 1_000;
+
+fn is_pot(c: char) -> bool {
+	c == '#' || c == '.'
+}
+
+// initial state: #..#.#..##......###...###
+fn parse_initial(line: &str) -> Result<String, ParseError> {
+	let mut cursor = Cursor::new(line);
+
+	cursor
+		.skip_whitespace()
+		.literal("initial state:", "`initial state:`")?;
+	let pots = cursor.skip_whitespace().take_while(is_pot, "pots")?;
+	cursor.end()?;
+
+	Ok(pots.to_owned())
+}
+
+// ...## => #
+fn parse_note(line: &str) -> Result<(String, String), ParseError> {
+	let mut cursor = Cursor::new(line);
+
+	let from = cursor.skip_whitespace().take_while(is_pot, "five pots")?;
+
+	if from.len() != 5 {
+		return Err(ParseError::invalid(line, from, "five pots"));
+	}
+
+	cursor.skip_whitespace().literal("=>", "`=>`")?;
+	let to = cursor.skip_whitespace().take_while(is_pot, "a pot")?;
+
+	if to.len() != 1 {
+		return Err(ParseError::invalid(line, to, "a pot"));
+	}
+
+	cursor.end()?;
+
+	// Plants appearing from empty pots would fill an infinite row
+	if from == "....." && to == "#" {
+		return Err(ParseError::invalid(line, to, "an empty pot"));
+	}
+
+	Ok((from.to_owned(), to.to_owned()))
+}
+
+/// The plants were still changing after `generations` generations, never
+/// repeating an earlier pattern.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Unsettled {
+	pub generations: u64,
+}
+
+impl fmt::Display for Unsettled {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"plants still changing after {} generations",
+			self.generations
+		)
+	}
+}
+
+impl error::Error for Unsettled {}
+
+/// Initial pots and the notes describing how each generation spreads.
+#[derive(Debug, PartialEq)]
+pub struct Day12 {
+	initial: String,
+	directions: HashMap<String, String>,
+}
+
+/// Writes the initial state, a blank line and then the notes in sorted order.
+impl fmt::Display for Day12 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut notes = self.directions.iter().collect::<Vec<_>>();
+
+		notes.sort();
+
+		writeln!(f, "initial state: {}", self.initial)?;
+
+		for (from, to) in notes {
+			write!(f, "\n{} => {}", from, to)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl FromStr for Day12 {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		let mut lines = parse::lines(s);
+		let (number, line) = lines.next().ok_or(ParseError::Empty)?;
+		let initial = parse_initial(line).map_err(|err| err.on_line(number))?;
+
+		let directions = lines
+			.map(|(number, line)| {
+				parse_note(line).map_err(|err| err.on_line(number))
+			})
+			.collect::<Result<_, _>>()?;
+
+		Ok(Self {
+			initial,
+			directions,
+		})
+	}
+}
+
+impl Day12 {
+	/// Generations reported by the binary on the way to part 2.
+	pub const CHECKPOINTS: [u64; 4] = [20, 500, 5_000, 50_000];
+
+	fn generation(&self, s: String) -> String {
+		s.chars()
+			.enumerate()
+			.map(|(i, _)| {
+				if i < 2 || i + 3 > s.len() {
+					return ".".to_owned();
+				}
+
+				let sub = &s[(i - 2)..(i + 3)];
+				let d = self
+					.directions
+					.get(sub)
+					.map(|s| s.to_owned())
+					.unwrap_or_else(|| ".".to_owned());
+
+				d.to_owned()
+			})
+			.collect()
+	}
+
+	/// Pots after `n` generations, padded with `n + 5` empty pots on
+	/// either side.
+	pub fn iteration(&self, n: usize) -> String {
+		let pad = ".".repeat(n + 5);
+		let initial = pad.clone() + &self.initial + &pad;
+
+		(0..n).fold(initial, |acc, _| self.generation(acc))
+	}
+
+	/// Sum of the numbers of every pot containing a plant after `n`
+	/// generations (part 1 uses 20 generations).
+	pub fn count(&self, n: usize) -> i64 {
+		self.iteration(n)
+			.chars()
+			.enumerate()
+			.map(|(i, c)| match c {
+				'#' => i as i64 - n as i64 - 5,
+				_ => 0,
+			})
+			.sum()
+	}
+
+	/// Sum of the numbers of every pot containing a plant after each of the
+	/// [`CHECKPOINTS`](Self::CHECKPOINTS), paired with the generation.
+	/// Fails like [`extrapolate`](Self::extrapolate).
+	pub fn checkpoints(&self) -> Result<Vec<(u64, i64)>, Unsettled> {
+		Self::CHECKPOINTS
+			.iter()
+			.map(|&n| Ok((n, self.extrapolate(n)?)))
+			.collect()
+	}
+
+	/// Sum of the numbers of every pot containing a plant after `n`
+	/// generations, for `n` far too large to simulate (part 2).
+	///
+	/// Simulates until the plants repeat a pattern seen in an earlier
+	/// generation, possibly shifted, and extrapolates from there. Fails if
+	/// no pattern repeats within `MAX_GENERATIONS` generations.
+	pub fn extrapolate(&self, n: u64) -> Result<i64, Unsettled> {
+		fn sum(pots: &str, offset: i64) -> i64 {
+			pots.chars()
+				.enumerate()
+				.filter(|(_, c)| *c == '#')
+				.map(|(i, _)| offset + i as i64)
+				.sum()
+		}
+
+		// Pattern and offset of every generation, and where each pattern
+		// was first seen
+		let mut history = Vec::<(String, i64)>::new();
+		let mut seen = HashMap::<String, u64>::new();
+		let mut pots = self.initial.clone();
+		let mut offset = 0;
+
+		for generation in 0..n {
+			if let Some(&start) = seen.get(&pots) {
+				let period = generation - start;
+				let shift = offset - history[start as usize].1;
+				let cycles = ((n - start) / period) as i64;
+				let (pots, offset) =
+					&history[(start + (n - start) % period) as usize];
+
+				return Ok(sum(pots, offset + shift * cycles));
+			}
+
+			if generation == MAX_GENERATIONS {
+				return Err(Unsettled {
+					generations: generation,
+				});
+			}
+
+			seen.insert(pots.clone(), generation);
+			history.push((pots.clone(), offset));
+
+			let next = self.generation(format!("....{}....", pots));
+
+			let (first, last) = match (next.find('#'), next.rfind('#')) {
+				(Some(first), Some(last)) => (first, last),
+				_ => return Ok(0),
+			};
+
+			offset = offset - 4 + first as i64;
+			pots = next[first..=last].to_owned();
+		}
+
+		Ok(sum(&pots, offset))
+	}
+}
+
+impl Solution for Day12 {
+	const DAY: u32 = 12;
+
+	type Part1 = i64;
+	type Part2 = i64;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<i64> {
+		Ok(self.count(20))
+	}
+
+	fn part2(&self) -> common::Result<i64> {
+		Ok(self.extrapolate(50_000_000_000)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::collection::hash_map;
+	use proptest::prelude::*;
+
+	static TEST_INPUT: &str = r#"
+        initial state: #..#.#..##......###...###
+
+        ...## => #
+        ..#.. => #
+        .#... => #
+        .#.#. => #
+        .#.## => #
+        .##.. => #
+        .#### => #
+        #.#.# => #
+        #.### => #
+        ##.#. => #
+        ##.## => #
+        ###.. => #
+        ###.# => #
+        ####. => #
+    "#;
+
+	#[test]
+	fn count() {
+		let day: Day12 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.count(20), 325);
+	}
+
+	#[test]
+	fn extrapolate() {
+		let day: Day12 = TEST_INPUT.parse().unwrap();
+
+		assert_eq!(day.extrapolate(20), Ok(325));
+		assert_eq!(day.extrapolate(500), Ok(day.count(500)));
+
+		// Plants that alternate between two patterns
+		let day: Day12 =
+			"initial state: ..#\n\n..#.. => #\n.#... => #\n..##. => #"
+				.parse()
+				.unwrap();
+
+		assert_eq!(day.extrapolate(7), Ok(day.count(7)));
+		assert_eq!(day.extrapolate(50_000_000_000), Ok(2));
+		assert_eq!(day.extrapolate(50_000_000_001), Ok(5));
+	}
+
+	#[test]
+	fn unsettled() {
+		// Rule 90, which grows a Sierpinski triangle that never repeats
+		let day: Day12 = "initial state: #\n\n\
+			...#. => #\n...## => #\n..##. => #\n..### => #\n\
+			.#... => #\n.#..# => #\n.##.. => #\n.##.# => #\n\
+			#..#. => #\n#..## => #\n#.##. => #\n#.### => #\n\
+			##... => #\n##..# => #\n###.. => #\n###.# => #"
+			.parse()
+			.unwrap();
+
+		assert_eq!(
+			day.extrapolate(50_000_000_000),
+			Err(Unsettled {
+				generations: MAX_GENERATIONS
+			})
+		);
+	}
+
+	#[test]
+	fn checkpoints() {
+		let day: Day12 = TEST_INPUT.parse().unwrap();
+		let checkpoints = day.checkpoints().unwrap();
+
+		assert_eq!(checkpoints[0], (20, 325));
+		assert_eq!(checkpoints[1], (500, day.count(500)));
+		assert_eq!(checkpoints.len(), Day12::CHECKPOINTS.len());
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"initial state: #..#\n\n..... => #"
+				.parse::<Day12>()
+				.unwrap_err(),
+			ParseError::Invalid {
+				line: 3,
+				column: 10,
+				text: "#".to_owned(),
+				expected: "an empty pot",
+			}
+		);
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(
+			initial in "[#.]{1,40}",
+			directions in hash_map("[#.]{5}", "[#.]", 0..32),
+		) {
+			let mut directions = directions;
+
+			directions.retain(|from, to| from != "....." || to != "#");
+
+			let day = Day12 { initial, directions };
+
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}
@@ -0,0 +1,222 @@
+//! Random track layouts with known answers
+
+use common::generate::{Generated, Rng};
+
+// Ticks to simulate before giving up on a layout whose carts never crash
+const MAX_TICKS: usize = 20_000;
+
+// A cart as its position, heading and number of intersections crossed
+struct Cart {
+	x: i64,
+	y: i64,
+	dx: i64,
+	dy: i64,
+	turns: usize,
+	crashed: bool,
+}
+
+// Draws the rectangles as loops of track, with `+` where two of them cross
+fn draw(rects: &[(i64, i64, i64, i64)], side: i64) -> Vec<Vec<u8>> {
+	let mut map = vec![vec![b' '; side as usize]; side as usize];
+	let mut put = |x: i64, y: i64, c: u8| {
+		let cell = &mut map[y as usize][x as usize];
+
+		*cell = match (*cell, c) {
+			(b'|', b'-') | (b'-', b'|') => b'+',
+			_ => c,
+		};
+	};
+
+	for &(left, top, right, bottom) in rects {
+		for x in (left + 1)..right {
+			put(x, top, b'-');
+			put(x, bottom, b'-');
+		}
+
+		for y in (top + 1)..bottom {
+			put(left, y, b'|');
+			put(right, y, b'|');
+		}
+
+		put(left, top, b'/');
+		put(right, top, b'\\');
+		put(left, bottom, b'\\');
+		put(right, bottom, b'/');
+	}
+
+	map
+}
+
+// Moves every cart in reading order until one is left, returning the first
+// crash and the last cart standing
+fn simulate(
+	map: &[Vec<u8>],
+	mut carts: Vec<Cart>,
+) -> Option<((i64, i64), (i64, i64))> {
+	let mut first = None;
+
+	for _ in 0..MAX_TICKS {
+		carts.sort_by_key(|cart| (cart.y, cart.x));
+
+		for i in 0..carts.len() {
+			if carts[i].crashed {
+				continue;
+			}
+
+			let cart = &mut carts[i];
+			let (dx, dy) = (cart.dx, cart.dy);
+
+			match map[cart.y as usize][cart.x as usize] {
+				b'/' => {
+					cart.dx = -dy;
+					cart.dy = -dx;
+				}
+				b'\\' => {
+					cart.dx = dy;
+					cart.dy = dx;
+				}
+				b'+' => {
+					match cart.turns % 3 {
+						0 => {
+							cart.dx = dy;
+							cart.dy = -dx;
+						}
+						2 => {
+							cart.dx = -dy;
+							cart.dy = dx;
+						}
+						_ => {}
+					}
+
+					cart.turns += 1;
+				}
+				_ => {}
+			}
+
+			cart.x += cart.dx;
+			cart.y += cart.dy;
+
+			let (x, y) = (cart.x, cart.y);
+			let hit = (0..carts.len()).find(|&j| {
+				j != i
+					&& !carts[j].crashed
+					&& (carts[j].x, carts[j].y) == (x, y)
+			});
+
+			if let Some(j) = hit {
+				carts[i].crashed = true;
+				carts[j].crashed = true;
+				first = first.or(Some((x, y)));
+			}
+		}
+
+		carts.retain(|cart| !cart.crashed);
+
+		if carts.len() <= 1 {
+			return Some((first?, carts.first().map(|cart| (cart.x, cart.y))?));
+		}
+	}
+
+	None
+}
+
+/// Generates `n` carts (an odd number, at least 3) on overlapping
+/// rectangular loops of track. Each rectangle's edges sit on rows and
+/// columns no other rectangle uses, so loops only meet at intersections.
+/// Layouts where the carts could chase each other forever are thrown away.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<String, String> {
+	let n = (n.max(3) - 1) / 2 * 2 + 1;
+	let num_rects = 2 + n / 3;
+	let side = 20 + 4 * n as i64;
+
+	loop {
+		let mut xs = (0..side).collect::<Vec<_>>();
+		let mut ys = xs.clone();
+
+		rng.shuffle(&mut xs);
+		rng.shuffle(&mut ys);
+
+		let rects = (0..num_rects)
+			.map(|i| {
+				let (x0, x1) = (xs[2 * i], xs[2 * i + 1]);
+				let (y0, y1) = (ys[2 * i], ys[2 * i + 1]);
+
+				(x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1))
+			})
+			.collect::<Vec<_>>();
+
+		let track = draw(&rects, side);
+		let mut straights = (0..side)
+			.flat_map(|y| (0..side).map(move |x| (x, y)))
+			.filter(|&(x, y)| {
+				matches!(track[y as usize][x as usize], b'-' | b'|')
+			})
+			.collect::<Vec<_>>();
+
+		if straights.len() < n {
+			continue;
+		}
+
+		rng.shuffle(&mut straights);
+
+		let mut map = track.clone();
+		let carts = straights[..n]
+			.iter()
+			.map(|&(x, y)| {
+				let sign = if rng.one_in(2) { 1 } else { -1 };
+				let cell = &mut map[y as usize][x as usize];
+				let (dx, dy) =
+					if *cell == b'-' { (sign, 0) } else { (0, sign) };
+
+				*cell = match (dx, dy) {
+					(1, _) => b'>',
+					(-1, _) => b'<',
+					(_, 1) => b'v',
+					_ => b'^',
+				};
+
+				Cart {
+					x,
+					y,
+					dx,
+					dy,
+					turns: 0,
+					crashed: false,
+				}
+			})
+			.collect::<Vec<_>>();
+
+		let lines = map
+			.into_iter()
+			.map(|row| String::from_utf8(row).unwrap())
+			.collect::<Vec<_>>();
+
+		if let Some(((x1, y1), (x2, y2))) = simulate(&track, carts) {
+			return Generated {
+				input: lines.join("\n"),
+				part1: Some(format!("{},{}", x1, y1)),
+				part2: Some(format!("{},{}", x2, y2)),
+			};
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day13;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(13);
+
+		for n in &[3, 5, 11, 21] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day13 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+			assert_eq!(day.part2().ok(), generated.part2);
+		}
+	}
+}
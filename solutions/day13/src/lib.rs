@@ -0,0 +1,712 @@
+//! Day 13: Mine Cart Madness
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::ParseError;
+use common::Solution;
+use grid::Grid;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Most ticks worth running before giving up on the carts ever crashing
+const MAX_TICKS: usize = 100_000;
+
+/// Direction a cart is facing.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+	Left,
+	Right,
+	Up,
+	Down,
+}
+
+impl fmt::Display for Direction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let c = match self {
+			Direction::Left => '<',
+			Direction::Right => '>',
+			Direction::Up => '^',
+			Direction::Down => 'v',
+		};
+
+		write!(f, "{}", c)
+	}
+}
+
+/// A mine cart.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cart {
+	pub direction: Direction,
+	/// Number of intersections crossed, modulo 3; carts turn left, go
+	/// straight and then turn right in turn.
+	pub turns: u32,
+	visited: bool,
+}
+
+impl Cart {
+	/// Creates a cart that has not yet crossed an intersection.
+	pub fn new(direction: Direction) -> Self {
+		Self {
+			direction,
+			turns: 0,
+			visited: false,
+		}
+	}
+}
+
+impl fmt::Display for Cart {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.direction)
+	}
+}
+
+/// Shape of a piece of track.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TrackType {
+	/// `/`
+	CurveForward,
+	/// `\`
+	CurveBackward,
+	/// `-`
+	Horizontal,
+	/// `|`
+	Vertical,
+	/// `+`
+	Intersection,
+}
+
+impl fmt::Display for TrackType {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let c = match self {
+			TrackType::CurveForward => '/',
+			TrackType::CurveBackward => '\\',
+			TrackType::Horizontal => '-',
+			TrackType::Vertical => '|',
+			TrackType::Intersection => '+',
+		};
+
+		write!(f, "{}", c)
+	}
+}
+
+/// A piece of track and the cart currently on it, if any.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+	pub track_type: TrackType,
+	pub cart: Option<Cart>,
+}
+
+impl Track {
+	/// Creates an empty piece of track.
+	pub fn new(track_type: TrackType) -> Self {
+		Self {
+			track_type,
+			cart: None,
+		}
+	}
+
+	/// Creates the straight piece of track a cart is drawn on top of.
+	pub fn from_cart(cart: Cart) -> Self {
+		let track_type = match cart.direction {
+			Direction::Left | Direction::Right => TrackType::Horizontal,
+			Direction::Up | Direction::Down => TrackType::Vertical,
+		};
+
+		Self {
+			track_type,
+			cart: Some(cart),
+		}
+	}
+}
+
+impl fmt::Display for Track {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if let Some(cart) = &self.cart {
+			cart.fmt(f)
+		} else {
+			self.track_type.fmt(f)
+		}
+	}
+}
+
+/// The carts were still running after `ticks` ticks, neither crashing nor
+/// looping back to where they'd been.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Unsettled {
+	pub ticks: usize,
+}
+
+impl fmt::Display for Unsettled {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "carts still running after {} ticks", self.ticks)
+	}
+}
+
+impl error::Error for Unsettled {}
+
+/// The track layout; `None` where there is no track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Day13 {
+	map: Grid<Option<Track>>,
+	num_carts: usize,
+}
+
+impl fmt::Display for Day13 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for row in self.map.rows() {
+			for track in row.iter() {
+				if let Some(track) = track {
+					track.fmt(f)?;
+				} else {
+					write!(f, " ")?;
+				}
+			}
+
+			writeln!(f)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Day13 {
+	/// Creates a track layout, failing on the first cart in reading order
+	/// that would run off the track.
+	pub fn new(map: Grid<Option<Track>>) -> Result<Self, ParseError> {
+		let mut num_carts = 0;
+
+		for ((x, y), track) in map.iter() {
+			let cart = match track {
+				Some(Track {
+					cart: Some(cart), ..
+				}) => cart,
+				_ => continue,
+			};
+
+			let state = (x, y, cart.direction.clone(), cart.turns % 3);
+
+			if route(&map, state).is_none() {
+				return Err(ParseError::Invalid {
+					line: y + 1,
+					column: x + 1,
+					text: cart.to_string(),
+					expected: "a cart that stays on the track",
+				});
+			}
+
+			num_carts += 1;
+		}
+
+		Ok(Self { map, num_carts })
+	}
+
+	/// The track layout.
+	pub fn map(&self) -> &Grid<Option<Track>> {
+		&self.map
+	}
+
+	/// Number of carts that have not crashed.
+	pub fn num_carts(&self) -> usize {
+		self.num_carts
+	}
+
+	/// Number of rows and columns.
+	pub fn dimensions(&self) -> (usize, usize) {
+		(self.map.height(), self.map.width())
+	}
+
+	/// Moves every cart one step, top to bottom and left to right, and
+	/// returns the `(x, y)` location of each crash. Crashed carts are
+	/// removed from the track. Every cart stays on the track, as that's
+	/// checked when the layout is created.
+	pub fn tick(&mut self) -> Vec<(usize, usize)> {
+		let mut collisions = Vec::new();
+		let (rows, cols) = self.dimensions();
+
+		for (_, track) in self.map.iter_mut() {
+			if let Some(Track {
+				cart: Some(cart), ..
+			}) = track
+			{
+				cart.visited = false;
+			}
+		}
+
+		for y in 0..rows {
+			for x in 0..cols {
+				let next = match &self.map[(x, y)] {
+					Some(Track {
+						track_type,
+						cart: Some(cart),
+					}) if !cart.visited => {
+						let state = (x, y, cart.direction.clone(), cart.turns);
+
+						step(track_type, state)
+					}
+					_ => None,
+				};
+
+				// Carts that are checked to stay on the track always have
+				// somewhere to go
+				let next = next.filter(|&(i, j, ..)| {
+					self.map.get(i, j).is_some_and(Option::is_some)
+				});
+				let (i, j, direction, turns) = match next {
+					Some(next) => next,
+					None => continue,
+				};
+
+				let cart =
+					self.map[(x, y)].as_mut().and_then(|t| t.cart.take());
+				let track = self.map.get_mut(i, j).and_then(Option::as_mut);
+
+				if let (Some(mut cart), Some(track)) = (cart, track) {
+					if track.cart.take().is_some() {
+						collisions.push((i, j));
+					} else {
+						cart.direction = direction;
+						cart.turns = turns;
+						cart.visited = true;
+						track.cart = Some(cart);
+					}
+				}
+			}
+		}
+
+		self.num_carts -= 2 * collisions.len();
+
+		collisions
+	}
+
+	// State of every cart, in reading order
+	fn carts(&self) -> Vec<State> {
+		self.map
+			.iter()
+			.filter_map(|((x, y), track)| {
+				let cart = track.as_ref()?.cart.as_ref()?;
+
+				Some((x, y, cart.direction.clone(), cart.turns % 3))
+			})
+			.collect()
+	}
+
+	// Ticks after which the carts can no longer crash if they haven't yet,
+	// as every one of them has settled into a loop and they've all gone
+	// round together, or `None` if that's too many to count
+	fn quiet_after(&self) -> Option<usize> {
+		let mut settled = 0;
+		let mut period = 1;
+
+		for state in self.carts() {
+			let (start, len) = route(&self.map, state)?;
+
+			settled = settled.max(start);
+			period = lcm(period, len)?;
+		}
+
+		settled.checked_add(period)
+	}
+
+	/// Location of the first crash on a copy of the track (part 1). Returns
+	/// `None` if the carts never crash, and fails if that can't be settled
+	/// within `MAX_TICKS` ticks.
+	pub fn first_collision(&self) -> Result<Option<(usize, usize)>, Unsettled> {
+		let mut day = self.clone();
+		let quiet = day.quiet_after();
+
+		for ticks in 0.. {
+			if quiet == Some(ticks) {
+				break;
+			}
+
+			if ticks == MAX_TICKS {
+				return Err(Unsettled { ticks });
+			}
+
+			if let Some(&collision) = day.tick().first() {
+				return Ok(Some(collision));
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Location of the last cart on a copy of the track, as with
+	/// [`simulate`](Self::simulate) (part 2).
+	pub fn last_cart(&self) -> Result<Option<(usize, usize)>, Unsettled> {
+		self.clone().simulate()
+	}
+
+	/// Runs until at most one cart remains and returns its location
+	/// (part 2). Returns `None` if every cart crashes or the carts never
+	/// get down to one, failing like
+	/// [`first_collision`](Self::first_collision).
+	pub fn simulate(&mut self) -> Result<Option<(usize, usize)>, Unsettled> {
+		let mut quiet = self.quiet_after();
+		let mut ticks = 0;
+
+		while self.num_carts > 1 {
+			if quiet == Some(ticks) {
+				return Ok(None);
+			}
+
+			if ticks == MAX_TICKS {
+				return Err(Unsettled { ticks });
+			}
+
+			ticks += 1;
+
+			// Every crash leaves the rest to settle down all over again
+			if !self.tick().is_empty() {
+				quiet = self.quiet_after().and_then(|q| q.checked_add(ticks));
+			}
+		}
+
+		Ok(self.carts().first().map(|&(x, y, _, _)| (x, y)))
+	}
+}
+
+// Location, direction and turn count of a cart
+type State = (usize, usize, Direction, u32);
+
+// Greatest common divisor of `a` and `b`
+fn gcd(a: usize, b: usize) -> usize {
+	if b == 0 {
+		a
+	} else {
+		gcd(b, a % b)
+	}
+}
+
+// Least common multiple of `a` and `b`, if it fits
+fn lcm(a: usize, b: usize) -> Option<usize> {
+	(a / gcd(a, b)).checked_mul(b)
+}
+
+// Direction and turn count of a cart leaving a piece of track, or `None` if
+// the cart can't travel along it
+fn turn(
+	track_type: &TrackType,
+	direction: &Direction,
+	turns: u32,
+) -> Option<(Direction, u32)> {
+	use Direction::*;
+
+	let direction = match (track_type, direction) {
+		(TrackType::CurveForward, Up) => Right,
+		(TrackType::CurveForward, Down) => Left,
+		(TrackType::CurveForward, Left) => Down,
+		(TrackType::CurveForward, Right) => Up,
+		(TrackType::CurveBackward, Up) => Left,
+		(TrackType::CurveBackward, Down) => Right,
+		(TrackType::CurveBackward, Left) => Up,
+		(TrackType::CurveBackward, Right) => Down,
+		(TrackType::Horizontal, Left) | (TrackType::Horizontal, Right) => {
+			direction.clone()
+		}
+		(TrackType::Vertical, Up) | (TrackType::Vertical, Down) => {
+			direction.clone()
+		}
+		(TrackType::Horizontal, _) | (TrackType::Vertical, _) => return None,
+		(TrackType::Intersection, _) => {
+			let turns = turns % 3 + 1;
+			let direction = match (turns % 3, direction) {
+				(1, Up) | (0, Down) => Left,
+				(1, Down) | (0, Up) => Right,
+				(1, Left) | (0, Right) => Down,
+				(1, Right) | (0, Left) => Up,
+				_ => direction.clone(),
+			};
+
+			return Some((direction, turns));
+		}
+	};
+
+	Some((direction, turns))
+}
+
+// Location one step from `(x, y)`, if it isn't off the top or left edge
+fn forward(
+	direction: &Direction,
+	x: usize,
+	y: usize,
+) -> Option<(usize, usize)> {
+	match direction {
+		Direction::Left => Some((x.checked_sub(1)?, y)),
+		Direction::Right => Some((x.checked_add(1)?, y)),
+		Direction::Up => Some((x, y.checked_sub(1)?)),
+		Direction::Down => Some((x, y.checked_add(1)?)),
+	}
+}
+
+// State of a cart one step on from `state` along `track_type`, or `None` if
+// it can't travel along it or would leave the top or left edge
+fn step(
+	track_type: &TrackType,
+	(x, y, direction, turns): State,
+) -> Option<State> {
+	let (direction, turns) = turn(track_type, &direction, turns)?;
+	let (x, y) = forward(&direction, x, y)?;
+
+	Some((x, y, direction, turns % 3))
+}
+
+// Ticks before a cart in `state` settles into a loop and the length of the
+// loop, ignoring any other carts it might crash into, or `None` if it would
+// run off the track first
+fn route(
+	map: &Grid<Option<Track>>,
+	mut state: State,
+) -> Option<(usize, usize)> {
+	let mut seen = HashMap::new();
+
+	loop {
+		let ticks = seen.len();
+
+		if let Some(start) = seen.insert(state.clone(), ticks) {
+			return Some((start, ticks - start));
+		}
+
+		let track = map.get(state.0, state.1)?.as_ref()?;
+
+		state = step(&track.track_type, state)?;
+	}
+}
+
+impl FromStr for Day13 {
+	type Err = ParseError;
+
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		if s.trim().is_empty() {
+			return Err(ParseError::Empty);
+		}
+
+		let rows = s
+			.lines()
+			.enumerate()
+			.map(|(i, line)| {
+				line.char_indices().map(move |(j, c)| match c {
+					'/' => Ok(Some(Track::new(TrackType::CurveForward))),
+					'\\' => Ok(Some(Track::new(TrackType::CurveBackward))),
+					'-' => Ok(Some(Track::new(TrackType::Horizontal))),
+					'|' => Ok(Some(Track::new(TrackType::Vertical))),
+					'+' => Ok(Some(Track::new(TrackType::Intersection))),
+					' ' => Ok(None),
+					'<' => {
+						Ok(Some(Track::from_cart(Cart::new(Direction::Left))))
+					}
+					'>' => {
+						Ok(Some(Track::from_cart(Cart::new(Direction::Right))))
+					}
+					'^' => Ok(Some(Track::from_cart(Cart::new(Direction::Up)))),
+					'v' => {
+						Ok(Some(Track::from_cart(Cart::new(Direction::Down))))
+					}
+					_ => Err(ParseError::invalid(
+						line,
+						&line[j..(j + c.len_utf8())],
+						"a track or cart",
+					)
+					.on_line(i + 1)),
+				})
+			})
+			.map(|line| line.collect::<Result<Vec<_>, _>>())
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Self::new(Grid::from_rows(rows, None))
+	}
+}
+
+impl Solution for Day13 {
+	const DAY: u32 = 13;
+
+	type Part1 = String;
+	type Part2 = String;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<String> {
+		Ok(match self.first_collision()? {
+			Some((x, y)) => format!("{},{}", x, y),
+			None => "none".to_owned(),
+		})
+	}
+
+	fn part2(&self) -> common::Result<String> {
+		Ok(match self.last_cart()? {
+			Some((x, y)) => format!("{},{}", x, y),
+			None => "none".to_owned(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::collection::vec;
+	use proptest::prelude::*;
+
+	static COLLISION_INPUT: &str = r#"/->-\
+|   |  /----\
+| /-+--+-\  |
+| | |  | v  |
+\-+-/  \-+--/
+  \------/"#;
+
+	static LAST_CAR_INPUT: &str = r#"/>-<\  
+|   |  
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/"#;
+
+	#[test]
+	fn collision() {
+		let mut day: Day13 = COLLISION_INPUT.parse().unwrap();
+
+		loop {
+			let collisions = day.tick();
+			if !collisions.is_empty() {
+				let collision = collisions[0];
+
+				assert_eq!(collision, (7, 3));
+				break;
+			}
+		}
+	}
+
+	#[test]
+	fn first_collision() {
+		let day: Day13 = COLLISION_INPUT.parse().unwrap();
+
+		assert_eq!(day.first_collision(), Ok(Some((7, 3))));
+		assert_eq!(day.first_collision(), Ok(Some((7, 3))));
+	}
+
+	#[test]
+	fn simulate() {
+		let mut day: Day13 = LAST_CAR_INPUT.parse().unwrap();
+		let position = day.simulate();
+
+		assert_eq!(position, Ok(Some((6, 4))));
+
+		let day: Day13 = LAST_CAR_INPUT.parse().unwrap();
+
+		assert_eq!(day.last_cart(), Ok(Some((6, 4))));
+	}
+
+	#[test]
+	fn never_collide() {
+		let day: Day13 = "/>->-\\\n|    |\n\\----/".parse().unwrap();
+
+		assert_eq!(day.part1().unwrap(), "none");
+		assert_eq!(day.part2().unwrap(), "none");
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"/->-\\\n| # |".parse::<Day13>().unwrap_err(),
+			ParseError::Invalid {
+				line: 2,
+				column: 3,
+				text: "#".to_owned(),
+				expected: "a track or cart",
+			}
+		);
+
+		assert_eq!(
+			"/-\\\n<-/".parse::<Day13>().unwrap_err(),
+			ParseError::Invalid {
+				line: 2,
+				column: 1,
+				text: "<".to_owned(),
+				expected: "a cart that stays on the track",
+			}
+		);
+
+		assert_eq!(
+			"/-\\\n\\v/".parse::<Day13>().unwrap_err(),
+			ParseError::Invalid {
+				line: 2,
+				column: 2,
+				text: "v".to_owned(),
+				expected: "a cart that stays on the track",
+			}
+		);
+	}
+
+	// Carts are always drawn on straight track, which is all that's left
+	// after parsing them
+	fn track() -> impl Strategy<Value = Option<Track>> {
+		let track = prop_oneof![
+			Just(TrackType::CurveForward),
+			Just(TrackType::CurveBackward),
+			Just(TrackType::Horizontal),
+			Just(TrackType::Vertical),
+			Just(TrackType::Intersection),
+		]
+		.prop_map(Track::new);
+
+		let cart = prop_oneof![
+			Just(Direction::Left),
+			Just(Direction::Right),
+			Just(Direction::Up),
+			Just(Direction::Down),
+		]
+		.prop_map(|direction| Track::from_cart(Cart::new(direction)));
+
+		prop_oneof![
+			2 => Just(None),
+			4 => track.prop_map(Some),
+			1 => cart.prop_map(Some),
+		]
+	}
+
+	// Rows of different lengths, some ending in empty space or empty
+	// altogether, with at least one piece of track somewhere. Carts that
+	// would run off the track are taken off it.
+	fn map() -> impl Strategy<Value = Grid<Option<Track>>> {
+		vec(vec(track(), 0..12), 1..8)
+			.prop_filter("a map without track", |map| {
+				map.iter().flatten().any(Option::is_some)
+			})
+			.prop_map(|rows| {
+				let mut map = Grid::from_rows(rows, None);
+				let derailed = map
+					.iter()
+					.filter_map(|((x, y), track)| {
+						let cart = track.as_ref()?.cart.as_ref()?;
+
+						Some((x, y, cart.direction.clone(), 0))
+					})
+					.filter(|state| route(&map, state.clone()).is_none())
+					.map(|(x, y, _, _)| (x, y))
+					.collect::<Vec<_>>();
+
+				for xy in derailed {
+					if let Some(track) = &mut map[xy] {
+						track.cart = None;
+					}
+				}
+
+				map
+			})
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(map in map()) {
+			let day = Day13::new(map).unwrap();
+
+			prop_assert_eq!(day.to_string().parse::<Day13>(), Ok(day));
+		}
+	}
+}
@@ -0,0 +1,70 @@
+//! Random numbers of recipes with known answers
+
+use common::generate::{Generated, Rng};
+
+// Scoreboard of at least `len` recipes
+fn scoreboard(len: usize) -> Vec<u8> {
+	let mut recipes = vec![3, 7];
+	let (mut first, mut second) = (0, 1);
+
+	while recipes.len() < len {
+		let total = recipes[first] + recipes[second];
+
+		if total >= 10 {
+			recipes.push(total / 10);
+		}
+
+		recipes.push(total % 10);
+		first = (first + usize::from(recipes[first]) + 1) % recipes.len();
+		second = (second + usize::from(recipes[second]) + 1) % recipes.len();
+	}
+
+	recipes
+}
+
+/// Generates a number of recipes below `n` (at least 10) whose digits are
+/// taken from somewhere in the first `n` recipes, so the digits are sure to
+/// appear. The number may start with a zero.
+pub fn generate(rng: &mut Rng, n: usize) -> Generated<String, usize> {
+	let n = n.max(10);
+	let digits = n.to_string().len() - 1;
+	let recipes = scoreboard(n + 10);
+	let start = rng.below(n - digits);
+	let pattern = &recipes[start..(start + digits)];
+
+	let value = pattern.iter().fold(0, |acc, &d| acc * 10 + usize::from(d));
+	let part1 = recipes[value..(value + 10)]
+		.iter()
+		.map(|d| d.to_string())
+		.collect();
+	let part2 = recipes
+		.windows(digits)
+		.position(|window| window == pattern)
+		.unwrap();
+
+	Generated {
+		input: pattern.iter().map(|d| d.to_string()).collect(),
+		part1: Some(part1),
+		part2: Some(part2),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Day14;
+	use common::Solution;
+
+	#[test]
+	fn generate() {
+		let mut rng = Rng::new(14);
+
+		for n in &[10, 100, 100, 10_000, 100_000] {
+			let generated = super::generate(&mut rng, *n);
+			let day: Day14 = generated.input.parse().unwrap();
+
+			assert_eq!(day.part1().ok(), generated.part1);
+			assert_eq!(day.part2().ok(), generated.part2);
+		}
+	}
+}
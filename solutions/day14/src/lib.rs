@@ -0,0 +1,298 @@
+//! Day 14: Chocolate Charts
+
+use std::fmt;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, ParseError};
+use common::Solution;
+
+#[cfg(feature = "generate")]
+pub mod generate;
+
+// Most recipes worth scoring before giving up on finding a sequence
+const MAX_RECIPES: usize = 1 << 25;
+
+/// An elf and the index of its current recipe.
+#[derive(Debug, PartialEq)]
+pub struct Elf {
+	pub position: usize,
+}
+
+impl Elf {
+	/// Creates an elf starting on the recipe at `position`.
+	pub fn new(position: usize) -> Self {
+		Self { position }
+	}
+}
+
+/// The scoreboard of recipes and the elves working through it.
+#[derive(Debug, PartialEq)]
+pub struct Day14 {
+	input: Vec<usize>,
+	elves: Vec<Elf>,
+	recipes: Vec<usize>,
+}
+
+impl Default for Day14 {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Day14 {
+	/// Creates a scoreboard with the initial recipes `3` and `7`.
+	pub fn new() -> Self {
+		let recipes = vec![3, 7];
+		let elves = recipes
+			.iter()
+			.enumerate()
+			.map(|(i, _)| Elf::new(i))
+			.collect();
+
+		Self {
+			input: Vec::new(),
+			recipes,
+			elves,
+		}
+	}
+
+	/// Elves on the scoreboard
+	pub fn elves(&self) -> &[Elf] {
+		&self.elves
+	}
+
+	/// Recipe scores created so far
+	pub fn recipes(&self) -> &[usize] {
+		&self.recipes
+	}
+
+	/// Combines the elves' current recipes, appends the new recipes to the
+	/// scoreboard, moves each elf and returns the number of recipes added.
+	pub fn next_recipes(&mut self) -> usize {
+		let total: usize = self
+			.elves
+			.iter()
+			.map(|elf| self.recipes[elf.position])
+			.sum();
+
+		let mut n = total;
+		let mut digit = 1;
+		let recipes = (0..)
+			.scan(0, |_, i| {
+				if i > 0 && digit > total {
+					return None;
+				}
+
+				let m = n % 10;
+
+				n /= 10;
+				digit *= 10;
+
+				Some(m)
+			})
+			.collect::<Vec<_>>();
+
+		let added = recipes.len();
+
+		self.recipes.extend(recipes.iter().rev());
+
+		for i in 0..self.elves.len() {
+			let elf = &mut self.elves[i];
+			let score = self.recipes[elf.position];
+
+			elf.position = (elf.position + score + 1) % self.recipes.len()
+		}
+
+		added
+	}
+
+	/// Scores of the ten recipes after the first `n` (part 1), unless that
+	/// takes more than `MAX_RECIPES` recipes.
+	pub fn simulate(&mut self, n: usize) -> Option<&[usize]> {
+		let end = n.checked_add(10).filter(|&end| end <= MAX_RECIPES)?;
+
+		while self.recipes.len() < end {
+			self.next_recipes();
+		}
+
+		Some(&self.recipes[n..end])
+	}
+
+	/// Number of recipes to the left of the first appearance of `input`
+	/// (part 2), if it appears within the first `MAX_RECIPES` recipes.
+	pub fn simulate_two(&mut self, input: &[usize]) -> Option<usize> {
+		// Every window starting before this has already been checked
+		let mut checked = 0;
+
+		loop {
+			let end = (self.recipes.len() + 1).saturating_sub(input.len());
+
+			for i in checked..end {
+				if &self.recipes[i..(i + input.len())] == input {
+					return Some(i);
+				}
+			}
+
+			if self.recipes.len() >= MAX_RECIPES {
+				return None;
+			}
+
+			checked = checked.max(end);
+			self.next_recipes();
+		}
+	}
+}
+
+/// Writes the digits of the number of recipes, keeping any leading zeros.
+impl fmt::Display for Day14 {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.input
+			.iter()
+			.try_for_each(|digit| write!(f, "{}", digit))
+	}
+}
+
+impl FromStr for Day14 {
+	type Err = ParseError;
+
+	/// Parses a number of recipes, failing if scoring the ten after it
+	/// takes more than `MAX_RECIPES` recipes.
+	fn from_str(s: &str) -> Result<Self, ParseError> {
+		parse::single_line(s, |line| {
+			let mut cursor = Cursor::new(line);
+			let digits = cursor
+				.skip_whitespace()
+				.take_while(|c| c.is_ascii_digit(), "a number of recipes")?;
+
+			cursor.end()?;
+
+			let n = parse::token::<usize>(line, digits, "a number of recipes")?;
+
+			if n > MAX_RECIPES - 10 {
+				return Err(ParseError::invalid(
+					line,
+					digits,
+					"a smaller number of recipes",
+				));
+			}
+
+			let input = digits.bytes().map(|b| usize::from(b - b'0')).collect();
+
+			Ok(Self {
+				input,
+				..Self::new()
+			})
+		})
+	}
+}
+
+impl Solution for Day14 {
+	const DAY: u32 = 14;
+
+	type Part1 = String;
+	type Part2 = usize;
+
+	fn parse(input: &str) -> Result<Self, ParseError> {
+		input.parse()
+	}
+
+	fn part1(&self) -> common::Result<String> {
+		let n = self.input.iter().fold(0, |acc, d| acc * 10 + d);
+
+		Day14::new()
+			.simulate(n)
+			.map(|recipes| recipes.iter().map(|n| n.to_string()).collect())
+			.ok_or_else(|| "too many recipes to score".into())
+	}
+
+	fn part2(&self) -> common::Result<usize> {
+		Day14::new().simulate_two(&self.input).ok_or_else(|| {
+			format!("recipes not found in the first {}", MAX_RECIPES).into()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use proptest::prelude::*;
+
+	#[test]
+	fn simulate() {
+		assert_eq!(
+			Day14::new().simulate(9),
+			Some(&[5, 1, 5, 8, 9, 1, 6, 7, 7, 9][..])
+		);
+		assert_eq!(
+			Day14::new().simulate(5),
+			Some(&[0, 1, 2, 4, 5, 1, 5, 8, 9, 1][..])
+		);
+		assert_eq!(
+			Day14::new().simulate(18),
+			Some(&[9, 2, 5, 1, 0, 7, 1, 0, 8, 5][..])
+		);
+		assert_eq!(
+			Day14::new().simulate(2018),
+			Some(&[5, 9, 4, 1, 4, 2, 9, 8, 8, 2][..])
+		);
+	}
+
+	#[test]
+	fn simulate_two() {
+		assert_eq!(Day14::new().simulate_two(&[5, 1, 5, 8, 9]), Some(9));
+		assert_eq!(Day14::new().simulate_two(&[0, 1, 2, 4, 5]), Some(5));
+		assert_eq!(Day14::new().simulate_two(&[5, 9, 4, 1, 4]), Some(2018));
+
+		// Sequences starting in the initial recipes
+		assert_eq!(Day14::new().simulate_two(&[3, 7]), Some(0));
+		assert_eq!(Day14::new().simulate_two(&[3, 7, 1]), Some(0));
+	}
+
+	#[test]
+	fn too_many_recipes() {
+		assert_eq!(Day14::new().simulate(usize::MAX), None);
+		assert_eq!(Day14::new().simulate(MAX_RECIPES - 9), None);
+	}
+
+	// Skip test because scoring every recipe up to `MAX_RECIPES` requires
+	// `--release` to run in a reasonable amount of time.
+	#[test]
+	#[ignore]
+	fn not_found() {
+		// Scores are single digits
+		assert_eq!(Day14::new().simulate_two(&[10]), None);
+	}
+
+	#[test]
+	fn parse_error() {
+		assert_eq!(
+			"5l8".parse::<Day14>().unwrap_err(),
+			ParseError::Invalid {
+				line: 1,
+				column: 2,
+				text: "l8".to_owned(),
+				expected: "end of line",
+			}
+		);
+
+		assert_eq!(
+			"18446744073709551615".parse::<Day14>().unwrap_err(),
+			ParseError::Invalid {
+				line: 1,
+				column: 1,
+				text: "18446744073709551615".to_owned(),
+				expected: "a smaller number of recipes",
+			}
+		);
+	}
+
+	proptest! {
+		#[test]
+		fn round_trip(text in "[0-9]{1,7}") {
+			let day: Day14 = text.parse().unwrap();
+
+			prop_assert_eq!(day.to_string(), text);
+			prop_assert_eq!(day.to_string().parse(), Ok(day));
+		}
+	}
+}